@@ -2,6 +2,8 @@ use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
 
+use crate::formats::OutputFormat;
+
 // ============================================================================
 // CLI Arguments
 // ============================================================================
@@ -28,8 +30,17 @@ use std::path::PathBuf;
     windsurf-forecast --pick-timezone\n\n  \
     # Specify custom coordinates\n  \
     windsurf-forecast --lat 40.7128 --lng -74.0060\n\n  \
+    # Use a named spot from the config file\n  \
+    windsurf-forecast --spot bat-galim\n\n  \
+    # Fetch several spots at once and rank them by wind\n  \
+    windsurf-forecast --spot bat-galim,hilton,sdot-yam\n  \
+    windsurf-forecast --all-spots\n\n  \
     # Use custom config file\n  \
-    windsurf-forecast --config /path/to/config.toml\n\n\
+    windsurf-forecast --config /path/to/config.toml\n\n  \
+    # Compare all configured providers\n  \
+    windsurf-forecast --ensemble\n\n  \
+    # Wind in Beaufort, temperatures in Fahrenheit, waves in feet\n  \
+    windsurf-forecast --wind-unit beaufort --temp-unit F --height-unit ft\n\n\
     Note: days-ahead + first-day-offset must not exceed 7 to ensure reliable forecasts."
 )]
 pub struct Args {
@@ -41,14 +52,10 @@ pub struct Args {
     #[arg(long, default_value_t = 0, value_name = "N")]
     pub first_day_offset: i32,
 
-    /// Weather forecast provider to use
-    #[arg(
-        long,
-        default_value = "stormglass",
-        value_name = "PROVIDER",
-        help = get_provider_help()
-    )]
-    pub provider: String,
+    /// Weather forecast provider to use (default: the spot's provider, then
+    /// `default_provider` from the config file, then stormglass)
+    #[arg(long, value_name = "PROVIDER", help = get_provider_help())]
+    pub provider: Option<String>,
 
     /// Timezone for displaying timestamps (e.g., "UTC", "LOCAL", "America/New_York", "Asia/Jerusalem")
     /// Use "LOCAL" to automatically detect system timezone. Overrides timezone from config file and is persisted.
@@ -67,6 +74,21 @@ pub struct Args {
     #[arg(long, value_name = "PATH")]
     pub config_file_path: Option<PathBuf>,
 
+    /// Named spot from the [spots] config section (supplies coordinates and,
+    /// unless given explicitly, timezone and provider). Several comma-separated
+    /// names fetch every spot and rank them by wind.
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_delimiter = ',',
+        conflicts_with_all = ["lat", "lng"]
+    )]
+    pub spot: Vec<String>,
+
+    /// Fetch every spot from the [spots] config section and rank them by wind
+    #[arg(long, conflicts_with_all = ["spot", "lat", "lng"])]
+    pub all_spots: bool,
+
     /// Latitude for the forecast location
     #[arg(long, value_name = "LAT")]
     pub lat: Option<f64>,
@@ -75,8 +97,62 @@ pub struct Args {
     #[arg(long, value_name = "LNG")]
     pub lng: Option<f64>,
 
+    /// Wind speed unit for the output: knots, m/s, km/h, mph or beaufort
+    /// (default: knots)
+    #[arg(long, value_name = "UNIT")]
+    pub wind_unit: Option<String>,
+
+    /// Temperature unit for the output: C or F (default: C)
+    #[arg(long, value_name = "UNIT")]
+    pub temp_unit: Option<String>,
+
+    /// Wave height unit for the output: m or ft (default: m)
+    #[arg(long, value_name = "UNIT")]
+    pub height_unit: Option<String>,
+
+    /// Format of the forecast file
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+
+    /// Leave hours between sunset and sunrise out of the output
+    #[arg(long)]
+    pub drop_night: bool,
+
+    /// Also fetch tide extremes and hourly sea level (StormGlass only; each
+    /// run uses two more requests of the daily quota)
+    #[arg(long)]
+    pub tides: bool,
+
+    /// Neither read nor write the response cache
+    #[arg(long, conflicts_with = "refresh")]
+    pub no_cache: bool,
+
+    /// Fetch fresh data even if the cache has it, and update the cache
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Never use the network: serve the most recent cached response for the
+    /// location and provider, however old
+    #[arg(long, conflicts_with_all = ["no_cache", "refresh"])]
+    pub offline: bool,
+
+    /// Write every provider request and response to DIR as replayable
+    /// fixtures (API keys are redacted)
+    #[arg(long, value_name = "DIR", conflicts_with = "offline")]
+    pub record: Option<PathBuf>,
+
+    /// Answer provider requests from fixtures recorded with --record
+    /// instead of the network
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["record", "offline"])]
+    pub replay: Option<PathBuf>,
+
+    /// Run every configured provider and report per-hour spread statistics
+    /// (members and thresholds come from the [ensemble] config section)
+    #[arg(long)]
+    pub ensemble: bool,
+
     /// Save configuration to file after successful execution
-    /// (Applies to provider, timezone, and coordinates)
+    /// (Applies to provider, timezone, coordinates and units)
     #[arg(long)]
     pub save: bool,
 
@@ -91,6 +167,12 @@ impl Args {
     pub fn config_path_display(&self) -> Option<String> {
         self.config_file_path.as_ref().map(|p| p.display().to_string())
     }
+
+    /// True when the run covers more than one spot (`--all-spots` or a
+    /// comma-separated `--spot` list)
+    pub fn is_multi_spot(&self) -> bool {
+        self.all_spots || self.spot.len() > 1
+    }
 }
 
 // ============================================================================
@@ -100,15 +182,34 @@ impl Args {
 pub fn validate_args(args: &Args) -> Result<()> {
     validate_days_range(args.days_ahead, args.first_day_offset)?;
 
+    if args.ensemble && args.format != OutputFormat::Json {
+        anyhow::bail!("--ensemble only supports --format json (got {})", args.format.name());
+    }
+
+    if args.is_multi_spot() {
+        if args.ensemble {
+            anyhow::bail!("--ensemble cannot be combined with several spots");
+        }
+        if args.save {
+            anyhow::bail!("--save cannot be combined with several spots");
+        }
+        if args.format.prints_to_stdout() {
+            anyhow::bail!(
+                "--format {} cannot be combined with several spots",
+                args.format.name()
+            );
+        }
+    }
+
     Ok(())
 }
 
 fn validate_days_range(days_ahead: i32, first_day_offset: i32) -> Result<()> {
-    if days_ahead < 1 || days_ahead > 7 {
+    if !(1..=7).contains(&days_ahead) {
         anyhow::bail!("days-ahead must be between 1 and 7 (got {})", days_ahead);
     }
 
-    if first_day_offset < 0 || first_day_offset > 7 {
+    if !(0..=7).contains(&first_day_offset) {
         anyhow::bail!(
             "first-day-offset must be between 0 and 7 (got {})",
             first_day_offset
@@ -140,7 +241,7 @@ fn get_provider_help() -> String {
         "Weather forecast provider to use".to_string()
     } else {
         format!(
-            "Weather forecast provider to use\nAvailable providers:\n  {}",
+            "Weather forecast provider to use (default: spot or config default_provider, then stormglass)\nAvailable providers:\n  {}",
             providers.join("\n  ")
         )
     }
@@ -0,0 +1,142 @@
+//! Multi-spot ranking
+//!
+//! A batch run fetches several spots and writes one forecast file per spot.
+//! The index written next to them ranks the spots by their mean wind speed
+//! over the forecast window, highest first; the peak wind speed breaks ties.
+//! Spots without any wind speed values are ranked last.
+//!
+//! Values are taken as they appear in the output files, so all spots must
+//! have been converted to the same [`OutputUnits`](crate::units::OutputUnits).
+
+use serde::Serialize;
+use std::cmp::Ordering;
+
+use crate::forecast_provider::WeatherDataPoint;
+
+/// One spot's entry in the batch index
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpotRanking {
+    /// 1-based position in the index
+    pub rank: usize,
+
+    pub spot: String,
+
+    pub provider: String,
+
+    /// Forecast file written for this spot
+    pub file: String,
+
+    /// Number of hourly data points in the file
+    pub hours: usize,
+
+    #[serde(rename = "meanWindSpeed", skip_serializing_if = "Option::is_none")]
+    pub mean_wind_speed: Option<f64>,
+
+    #[serde(rename = "maxWindSpeed", skip_serializing_if = "Option::is_none")]
+    pub max_wind_speed: Option<f64>,
+
+    #[serde(rename = "maxGust", skip_serializing_if = "Option::is_none")]
+    pub max_gust: Option<f64>,
+}
+
+impl SpotRanking {
+    /// Summarize the wind of a spot's (already trimmed and converted) hours
+    ///
+    /// The entry is unranked (`rank` 0) until passed through [`rank_spots`].
+    pub fn from_hours(spot: &str, provider: &str, file: &str, hours: &[WeatherDataPoint]) -> Self {
+        let wind: Vec<f64> = hours
+            .iter()
+            .filter_map(|p| p.wind.wind_speed.map(|q| q.value()))
+            .collect();
+        let gust = hours.iter().filter_map(|p| p.wind.gust.map(|q| q.value()));
+
+        Self {
+            rank: 0,
+            spot: spot.to_string(),
+            provider: provider.to_string(),
+            file: file.to_string(),
+            hours: hours.len(),
+            mean_wind_speed: (!wind.is_empty())
+                .then(|| wind.iter().sum::<f64>() / wind.len() as f64),
+            max_wind_speed: wind.iter().copied().reduce(f64::max),
+            max_gust: gust.reduce(f64::max),
+        }
+    }
+}
+
+/// Compare two optional values so that larger values come first and `None` last
+fn descending(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Sort spots by the wind criterion and assign ranks
+pub fn rank_spots(mut spots: Vec<SpotRanking>) -> Vec<SpotRanking> {
+    spots.sort_by(|a, b| {
+        descending(a.mean_wind_speed, b.mean_wind_speed)
+            .then_with(|| descending(a.max_wind_speed, b.max_wind_speed))
+    });
+    for (i, spot) in spots.iter_mut().enumerate() {
+        spot.rank = i + 1;
+    }
+    spots
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::{convert_timezone, UtcTimestamp};
+    use crate::units::{Speed, SpeedUnit};
+    use chrono::{DateTime, Utc};
+
+    fn hours(winds: &[Option<f64>]) -> Vec<WeatherDataPoint> {
+        winds
+            .iter()
+            .enumerate()
+            .map(|(i, wind)| {
+                let utc = UtcTimestamp(DateTime::<Utc>::from_timestamp(i as i64 * 3600, 0).unwrap());
+                let mut point = WeatherDataPoint::empty(convert_timezone(utc, chrono_tz::UTC).unwrap());
+                point.wind.wind_speed = wind.map(|w| Speed::new(w, SpeedUnit::Knots));
+                point.wind.gust = wind.map(|w| Speed::new(w + 5.0, SpeedUnit::Knots));
+                point
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_hours_summarizes_wind() {
+        let ranking =
+            SpotRanking::from_hours("hilton", "openmeteo", "out.json", &hours(&[Some(10.0), None, Some(20.0)]));
+        assert_eq!(ranking.hours, 3);
+        assert_eq!(ranking.mean_wind_speed, Some(15.0));
+        assert_eq!(ranking.max_wind_speed, Some(20.0));
+        assert_eq!(ranking.max_gust, Some(25.0));
+
+        let calm = SpotRanking::from_hours("calm", "openmeteo", "out.json", &hours(&[None]));
+        assert_eq!(calm.mean_wind_speed, None);
+        assert_eq!(calm.max_gust, None);
+    }
+
+    #[test]
+    fn test_rank_spots_orders_by_mean_then_peak_with_missing_last() {
+        let spots = vec![
+            SpotRanking::from_hours("empty", "p", "a", &hours(&[None])),
+            SpotRanking::from_hours("steady", "p", "b", &hours(&[Some(15.0), Some(15.0)])),
+            SpotRanking::from_hours("gusty", "p", "c", &hours(&[Some(10.0), Some(20.0)])),
+            SpotRanking::from_hours("light", "p", "d", &hours(&[Some(8.0)])),
+        ];
+
+        let ranked = rank_spots(spots);
+        let order: Vec<(usize, &str)> = ranked.iter().map(|s| (s.rank, s.spot.as_str())).collect();
+        assert_eq!(
+            order,
+            vec![(1, "gusty"), (2, "steady"), (3, "light"), (4, "empty")]
+        );
+    }
+}
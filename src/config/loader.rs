@@ -13,12 +13,73 @@
 //! default_provider = "stormglass"
 //! lat = 32.486722
 //! lng = 34.888722
+//! wind_unit = "knots"     # knots, m/s, km/h, mph, beaufort
+//! temp_unit = "C"         # C, F
+//! height_unit = "m"       # m, ft
+//!
+//! # Optional: field → provider mapping for `--provider composite`
+//! [composite]
+//! wind = "windy"
+//! waves = "stormglass"
+//! clouds = "openweathermap"
+//!
+//! # Optional: named locations for `--spot <name>`
+//! [spots.bat-galim]
+//! lat = 32.8329
+//! lng = 34.9547
+//! timezone = "Asia/Jerusalem"   # optional, overrides [general] timezone
+//! provider = "windy"            # optional, overrides default_provider
+//! shore_bearing = 270           # optional, direction the beach faces (degrees)
+//! notes = "Rocks at low tide"   # optional
+//! tide_constituents = "tides/haifa.csv"  # optional, harmonic constants for offline tides
+//!
+//! # Optional: members and disagreement thresholds for `--ensemble`
+//! [ensemble]
+//! providers = ["stormglass", "openmeteo", "windy"]
+//! wind_speed_spread = 3.0
+//!
+//! # Optional: rider thresholds for hour scoring and session windows
+//! [rider]
+//! min_wind = 12.0               # knots
+//! max_wind = 30.0               # knots
+//! max_gust_factor = 1.5         # gust / wind speed
+//! allowed_directions = ["cross-onshore", "cross"]
+//! daylight_only = true
+//! weight = 75.0                # kg, enables sail and board recommendations
+//! skill = "beginner"            # beginner, intermediate, advanced
+//! sails = [4.5, 5.3, 6.2, 7.0]  # m²
+//!
+//! [[rider.boards]]
+//! name = "Freeride 130"
+//! volume = 130.0                # liters
+//!
+//! # Optional: provider response cache
+//! [cache]
+//! ttl_minutes = 180             # how long a cached response is reused
+//! dir = "/tmp/windsurf-cache"   # default: windsurf-forecast in the XDG cache dir
+//!
+//! # Optional: timeout and retries of provider requests
+//! [http]
+//! max_attempts = 3              # 1 disables retries
+//! timeout_seconds = 30          # per attempt
+//!
+//! # Optional: API base URLs, e.g. for a proxy, mirror or local stand-in
+//! [endpoints]
+//! stormglass = "https://proxy.example.com/stormglass/v2"
+//! openweathermap = "http://localhost:8080/data/3.0"
 //! ```
 //!
 //! # Structures
 //!
 //! - [`Config`]: Top-level configuration with `[general]` section
 //! - [`GeneralConfig`]: Application configuration fields
+//! - [`CompositeConfig`]: Field → provider mapping for the `composite` provider
+//! - [`EnsembleConfig`]: Member providers and thresholds for `--ensemble`
+//! - [`SpotConfig`]: A named location selected with `--spot`
+//! - [`RiderConfig`]: Thresholds that make an hour sailable, and the rider's
+//!   weight, skill and quiver for gear recommendations
+//! - [`CacheConfig`]: Lifetime and location of cached provider responses
+//! - [`HttpConfig`]: Timeout and retry attempts of provider requests
 //!
 //! # Functions
 //!
@@ -58,6 +119,7 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -69,6 +131,29 @@ use std::path::PathBuf;
 pub struct Config {
     #[serde(default)]
     pub general: GeneralConfig,
+
+    #[serde(default, skip_serializing_if = "CompositeConfig::is_empty")]
+    pub composite: CompositeConfig,
+
+    #[serde(default, skip_serializing_if = "EnsembleConfig::is_default")]
+    pub ensemble: EnsembleConfig,
+
+    /// Named locations, keyed by spot name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spots: BTreeMap<String, SpotConfig>,
+
+    #[serde(default, skip_serializing_if = "RiderConfig::is_default")]
+    pub rider: RiderConfig,
+
+    #[serde(default, skip_serializing_if = "CacheConfig::is_default")]
+    pub cache: CacheConfig,
+
+    #[serde(default, skip_serializing_if = "HttpConfig::is_default")]
+    pub http: HttpConfig,
+
+    /// API base URL overrides, keyed by provider name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub endpoints: BTreeMap<String, String>,
 }
 
 /// General configuration section
@@ -92,6 +177,18 @@ pub struct GeneralConfig {
     /// Longitude for forecast location (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lng: Option<f64>,
+
+    /// Output wind speed unit (optional, default: knots)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_unit: Option<String>,
+
+    /// Output temperature unit (optional, default: C)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_unit: Option<String>,
+
+    /// Output wave height unit (optional, default: m)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height_unit: Option<String>,
 }
 
 impl Default for GeneralConfig {
@@ -101,15 +198,336 @@ impl Default for GeneralConfig {
             default_provider: default_provider(),
             lat: None,
             lng: None,
+            wind_unit: None,
+            temp_unit: None,
+            height_unit: None,
         }
     }
 }
 
+/// A named location (`[spots.<name>]` section)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpotConfig {
+    pub lat: f64,
+
+    pub lng: f64,
+
+    /// Timezone for this spot (optional, overrides `[general]` timezone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// Preferred provider for this spot (optional, overrides `default_provider`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// Compass bearing the beach faces, from the shore out to sea (optional;
+    /// enables the onshore/offshore `shoreWind` classification)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shore_bearing: Option<f64>,
+
+    /// Free-form notes shown with the configuration summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// CSV of harmonic tide constituents for offline tide prediction
+    /// (optional; relative paths are relative to the config file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tide_constituents: Option<PathBuf>,
+}
+
+/// Composite provider section
+///
+/// Maps each section of a weather data point to the provider that should
+/// supply it. Unmapped sections are left empty in the merged output.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompositeConfig {
+    /// Provider for wind speed, direction and gust
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind: Option<String>,
+
+    /// Provider for swell and wind-wave data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waves: Option<String>,
+
+    /// Provider for cloud cover (total and layers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clouds: Option<String>,
+
+    /// Provider for air temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub air_temperature: Option<String>,
+
+    /// Provider for water temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub water_temperature: Option<String>,
+
+    /// Provider for precipitation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precipitation: Option<String>,
+}
+
+impl CompositeConfig {
+    /// All `(field, provider)` pairs, in a fixed order, including unmapped fields
+    pub fn entries(&self) -> [(&'static str, Option<&str>); 6] {
+        [
+            ("wind", self.wind.as_deref()),
+            ("waves", self.waves.as_deref()),
+            ("clouds", self.clouds.as_deref()),
+            ("air_temperature", self.air_temperature.as_deref()),
+            ("water_temperature", self.water_temperature.as_deref()),
+            ("precipitation", self.precipitation.as_deref()),
+        ]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().iter().all(|(_, provider)| provider.is_none())
+    }
+
+    /// Distinct provider names referenced by the mapping, in field order
+    pub fn providers(&self) -> Vec<&str> {
+        let mut providers: Vec<&str> = Vec::new();
+        for (_, provider) in self.entries() {
+            if let Some(name) = provider {
+                if !providers.contains(&name) {
+                    providers.push(name);
+                }
+            }
+        }
+        providers
+    }
+}
+
+/// Ensemble section
+///
+/// Spreads (max − min across providers) above these thresholds flag an hour
+/// as a disagreement. Wind thresholds are in m/s, swell in meters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleConfig {
+    /// Member providers; empty means every registered provider that can be
+    /// instantiated (i.e. whose API key is available)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<String>,
+
+    #[serde(default = "default_wind_speed_spread")]
+    pub wind_speed_spread: f64,
+
+    #[serde(default = "default_gust_spread")]
+    pub gust_spread: f64,
+
+    #[serde(default = "default_swell_height_spread")]
+    pub swell_height_spread: f64,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            providers: Vec::new(),
+            wind_speed_spread: default_wind_speed_spread(),
+            gust_spread: default_gust_spread(),
+            swell_height_spread: default_swell_height_spread(),
+        }
+    }
+}
+
+impl EnsembleConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Rider section
+///
+/// An hour is sailable when the wind speed is within `[min_wind, max_wind]`
+/// knots, gusts stay below `max_gust_factor` times the wind speed, the wind
+/// blows from one of `allowed_directions` and, with `daylight_only`, it is
+/// daytime. `weight`, `skill`, `sails` and `boards` size the recommended gear.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiderConfig {
+    #[serde(default = "default_min_wind")]
+    pub min_wind: f64,
+
+    #[serde(default = "default_max_wind")]
+    pub max_wind: f64,
+
+    #[serde(default = "default_max_gust_factor")]
+    pub max_gust_factor: f64,
+
+    /// Shore-relative directions (onshore, cross-onshore, cross,
+    /// cross-offshore, offshore); empty allows every direction. Only applied
+    /// at spots with a `shore_bearing`.
+    #[serde(default = "default_allowed_directions")]
+    pub allowed_directions: Vec<String>,
+
+    #[serde(default = "default_daylight_only")]
+    pub daylight_only: bool,
+
+    /// Rider weight in kg; gear recommendations are only made when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+
+    #[serde(default)]
+    pub skill: SkillLevel,
+
+    /// Sail sizes in m²; empty recommends an unconstrained size
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sails: Vec<f64>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boards: Vec<BoardConfig>,
+}
+
+/// Rider skill, which decides how conservatively gear is sized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillLevel {
+    Beginner,
+    #[default]
+    Intermediate,
+    Advanced,
+}
+
+/// A board in the rider's quiver (`[[rider.boards]]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardConfig {
+    pub name: String,
+
+    /// Volume in liters
+    pub volume: f64,
+}
+
+impl Default for RiderConfig {
+    fn default() -> Self {
+        Self {
+            min_wind: default_min_wind(),
+            max_wind: default_max_wind(),
+            max_gust_factor: default_max_gust_factor(),
+            allowed_directions: default_allowed_directions(),
+            daylight_only: default_daylight_only(),
+            weight: None,
+            skill: SkillLevel::default(),
+            sails: Vec::new(),
+            boards: Vec::new(),
+        }
+    }
+}
+
+impl RiderConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Cache section
+///
+/// Successful provider responses are reused for `ttl_minutes` by runs that
+/// ask for the same data. `dir` defaults to `windsurf-forecast` under the
+/// XDG cache directory (`~/.cache` on Linux).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    #[serde(default = "default_cache_ttl_minutes")]
+    pub ttl_minutes: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_minutes: default_cache_ttl_minutes(),
+            dir: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_cache_ttl_minutes() -> u64 {
+    180
+}
+
+/// HTTP section
+///
+/// Each provider request gets `timeout_seconds` per attempt and up to
+/// `max_attempts` attempts when it fails transiently (connection errors,
+/// timeouts, HTTP 429 and 5xx).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpConfig {
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            timeout_seconds: default_timeout_seconds(),
+        }
+    }
+}
+
+impl HttpConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_min_wind() -> f64 {
+    12.0
+}
+
+fn default_max_wind() -> f64 {
+    35.0
+}
+
+fn default_max_gust_factor() -> f64 {
+    1.6
+}
+
+/// Everything except offshore, which carries a rider away from the beach
+fn default_allowed_directions() -> Vec<String> {
+    ["onshore", "cross-onshore", "cross", "cross-offshore"]
+        .iter()
+        .map(|d| d.to_string())
+        .collect()
+}
+
+fn default_daylight_only() -> bool {
+    true
+}
+
+fn default_wind_speed_spread() -> f64 {
+    3.0
+}
+
+fn default_gust_spread() -> f64 {
+    4.0
+}
+
+fn default_swell_height_spread() -> f64 {
+    0.5
+}
+
 fn default_timezone() -> String {
     "UTC".to_string()
 }
 
-fn default_provider() -> String {
+pub(crate) fn default_provider() -> String {
     "stormglass".to_string()
 }
 
@@ -62,28 +62,65 @@ pub mod timezone;
 
 // Re-export commonly used items for convenience
 pub use types::ResolvedConfig;
-pub use resolver::resolve_from_args_and_file;
+pub use resolver::{resolve_from_args_and_file, resolve_spots_from_args_and_file};
 pub use loader::{load_config_from_file, save_config, get_default_config_path};
 pub use timezone::{check_timezone_match, pick_timezone_interactive};
 
 use anyhow::Result;
 use std::path::PathBuf;
 
+use crate::units::Unit;
+use types::ConfigSource;
+
 /// Save resolved configuration to file
 ///
 /// Converts ResolvedConfig back to Config structure and persists to TOML file.
 /// Only called when user provides --save flag.
+///
+/// The `[spots]` table is kept from the existing file. When the run used a
+/// spot, its coordinates and timezone are not copied into `[general]`, and
+/// neither is its provider unless it was chosen with `--provider`.
 pub fn save_config_from_resolved(
     resolved: &ResolvedConfig,
     path: Option<&PathBuf>
 ) -> Result<()> {
+    let existing = loader::load_config_from_file(path)?;
+    let (timezone, lat, lng) = match &resolved.spot {
+        Some(_) => (
+            existing.general.timezone.clone(),
+            existing.general.lat,
+            existing.general.lng,
+        ),
+        None => (
+            resolved.timezone.name().to_string(),
+            Some(resolved.lat),
+            Some(resolved.lng),
+        ),
+    };
+
+    let default_provider = if resolved.spot.is_some() && resolved.provider_source != ConfigSource::Cli {
+        existing.general.default_provider.clone()
+    } else {
+        resolved.provider.clone()
+    };
+
     let config = loader::Config {
         general: loader::GeneralConfig {
-            timezone: resolved.timezone.name().to_string(),
-            default_provider: resolved.provider.clone(),
-            lat: Some(resolved.lat),
-            lng: Some(resolved.lng),
+            timezone,
+            default_provider,
+            lat,
+            lng,
+            wind_unit: Some(resolved.units.wind.symbol().to_string()),
+            temp_unit: Some(resolved.units.temperature.symbol().to_string()),
+            height_unit: Some(resolved.units.height.symbol().to_string()),
         },
+        composite: resolved.composite.clone(),
+        ensemble: resolved.ensemble.clone(),
+        spots: existing.spots,
+        rider: resolved.rider.clone(),
+        cache: existing.cache,
+        http: existing.http,
+        endpoints: existing.endpoints,
     };
     
     loader::save_config(&config, path)?;
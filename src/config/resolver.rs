@@ -37,6 +37,14 @@
 //! - [`resolve_coordinates()`]: Applies precedence then validates
 //!   - Returns error if neither CLI nor config provides both lat AND lng
 //!
+//! ## Spots
+//!
+//! - [`resolve_spot()`]: Looks up a `[spots.<name>]` entry for `--spot`
+//!   - A spot supplies coordinates (instead of `--lat`/`--lng` and `[general]`),
+//!     and optionally the timezone and provider when not given on the CLI
+//! - [`resolve_spots_from_args_and_file()`]: One [`ResolvedConfig`] per spot
+//!   for `--all-spots` or a comma-separated `--spot` list
+//!
 //! ## Date Range Validation
 //!
 //! - [`validate_date_range()`]: Validates forecast parameters
@@ -44,6 +52,42 @@
 //!   - `first_day_offset`: 0-7 (inclusive)
 //!   - Business rule: `days_ahead + first_day_offset ≤ 7`
 //!
+//! ## Composite Provider Validation
+//!
+//! - [`validate_composite_config()`]: Validates the `[composite]` mapping
+//!   - At least one field must be mapped
+//!   - Every mapped provider must be registered (and not `composite` itself)
+//!
+//! - [`validate_ensemble_config()`]: Validates the `[ensemble]` section
+//!   - Listed providers must be registered (and not `composite`)
+//!   - Spread thresholds must be positive
+//!
+//! - [`validate_rider_config()`]: Validates the `[rider]` thresholds
+//!   - Wind range must be non-empty, gust factor at least 1.0
+//!   - Allowed directions must be shore-relative direction names
+//!   - Weight, sail sizes and board volumes must be positive
+//!
+//! ## Output Units
+//!
+//! - [`resolve_output_units()`]: Applies precedence to `--wind-unit`,
+//!   `--temp-unit` and `--height-unit`, then parses each unit name
+//!
+//! ## Response Cache
+//!
+//! - [`resolve_cache_settings()`]: Combines the `[cache]` section with
+//!   `--no-cache`, `--refresh` and `--offline`
+//! - [`resolve_fixture_mode()`]: Maps `--record` / `--replay` to a
+//!   [`FixtureMode`]
+//!
+//! ## HTTP
+//!
+//! - [`resolve_retry_policy()`]: Validates the `[http]` section
+//!
+//! ## Endpoints
+//!
+//! - [`resolve_endpoints()`]: Validates the `[endpoints]` section and applies
+//!   the providers' `*_API_URL` environment variables on top
+//!
 //! # Main Entry Point
 //!
 //! [`resolve_from_args_and_file()`] is the primary function that:
@@ -82,11 +126,17 @@
 //! ResolvedConfig { lat: 40.7, lng: -74.0, days_ahead: 3, ... }
 //! ```
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use crate::args::Args;
-use super::types::{ResolvedConfig, ConfigSource};
-use super::loader::{Config, load_config_from_file};
+use super::types::{ResolvedConfig, ResolvedSpot, ConfigSource};
+use super::loader::{CacheConfig, CompositeConfig, Config, EnsembleConfig, HttpConfig, RiderConfig, default_provider, get_default_config_path, load_config_from_file};
 use super::timezone::TimezoneConfig;
+use crate::http::{CacheMode, CacheSettings, FixtureMode, RetryPolicy};
+use crate::shore::ShoreWind;
+use crate::tide::TideTable;
+use crate::units::OutputUnits;
 
 /// Resolve a single configuration value using precedence rules
 ///
@@ -101,7 +151,6 @@ pub fn resolve<T>(cli: Option<T>, config: Option<T>, default: T) -> T {
 ///
 /// Same precedence as resolve(), but returns tuple: (value, source)
 /// Source indicates where value came from (CLI/Config/Default)
-#[allow(dead_code)] // Part of contract, will be used in future enhancements
 pub fn resolve_with_source<T: Clone>(
     cli: Option<T>,
     config: Option<T>,
@@ -165,6 +214,56 @@ pub fn resolve_coordinates(
     Ok((lat, lng))
 }
 
+/// Look up a named spot in the `[spots]` table and validate its coordinates
+///
+/// A relative `tide_constituents` path is taken relative to `config_dir`,
+/// the directory of the config file, so runs from cron or another working
+/// directory find the same file.
+pub fn resolve_spot(name: &str, config: &Config, config_dir: &Path) -> Result<ResolvedSpot> {
+    let Some(spot) = config.spots.get(name) else {
+        let available: Vec<&str> = config.spots.keys().map(String::as_str).collect();
+        if available.is_empty() {
+            anyhow::bail!(
+                "Unknown spot '{}'. No spots are configured; add a [spots.{}] section \
+                 with lat and lng to the config file.",
+                name,
+                name
+            );
+        }
+        anyhow::bail!(
+            "Unknown spot '{}'. Configured spots: {}",
+            name,
+            available.join(", ")
+        );
+    };
+
+    validate_coordinates(spot.lat, spot.lng)
+        .with_context(|| format!("Invalid coordinates for spot '{}'", name))?;
+
+    if let Some(bearing) = spot.shore_bearing {
+        if !(0.0..360.0).contains(&bearing) {
+            anyhow::bail!(
+                "Invalid shore_bearing for spot '{}': {} (must be between 0 and 360 degrees)",
+                name,
+                bearing
+            );
+        }
+    }
+
+    let tide_table = spot
+        .tide_constituents
+        .as_deref()
+        .map(|path| TideTable::load(&config_dir.join(path)))
+        .transpose()
+        .with_context(|| format!("Invalid tide_constituents for spot '{}'", name))?;
+
+    Ok(ResolvedSpot {
+        name: name.to_string(),
+        config: spot.clone(),
+        tide_table,
+    })
+}
+
 /// Validate date range parameters
 ///
 /// - days_ahead range: 1 to 7 (inclusive)
@@ -195,35 +294,336 @@ pub fn validate_date_range(days_ahead: i32, first_day_offset: i32) -> Result<()>
     Ok(())
 }
 
+/// Validate the `[composite]` field → provider mapping
+///
+/// Only called when the selected provider is "composite".
+pub fn validate_composite_config(composite: &CompositeConfig) -> Result<()> {
+    if composite.is_empty() {
+        anyhow::bail!(
+            "Provider 'composite' requires a [composite] section in the config file.\n\
+             Map fields to providers, for example:\n\
+             \n\
+             [composite]\n\
+             wind = \"windy\"\n\
+             waves = \"stormglass\"\n\
+             clouds = \"openweathermap\""
+        );
+    }
+
+    for (field, provider) in composite.entries() {
+        let Some(provider) = provider else { continue };
+        if provider == "composite" {
+            anyhow::bail!(
+                "Composite field '{}' cannot be mapped to the 'composite' provider itself",
+                field
+            );
+        }
+        crate::provider_registry::validate_provider_name(provider)
+            .with_context(|| format!("Invalid provider for composite field '{}'", field))?;
+    }
+
+    Ok(())
+}
+
+/// Validate the `[ensemble]` section
+///
+/// Only called when `--ensemble` is given.
+pub fn validate_ensemble_config(ensemble: &EnsembleConfig) -> Result<()> {
+    for provider in &ensemble.providers {
+        if provider == "composite" {
+            anyhow::bail!("The 'composite' provider cannot be an ensemble member");
+        }
+        crate::provider_registry::validate_provider_name(provider)
+            .context("Invalid provider in [ensemble] providers")?;
+    }
+
+    for (name, value) in [
+        ("wind_speed_spread", ensemble.wind_speed_spread),
+        ("gust_spread", ensemble.gust_spread),
+        ("swell_height_spread", ensemble.swell_height_spread),
+    ] {
+        if value.is_nan() || value <= 0.0 {
+            anyhow::bail!("Ensemble threshold {} must be positive (got {})", name, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the `[rider]` thresholds
+///
+/// - `min_wind` must be non-negative and below `max_wind`
+/// - `max_gust_factor` must be at least 1.0
+/// - `allowed_directions` must be shore-relative direction names
+/// - weight, sail sizes and board volumes must be positive
+pub fn validate_rider_config(rider: &RiderConfig) -> Result<()> {
+    if rider.min_wind.is_nan() || rider.min_wind < 0.0 || rider.min_wind >= rider.max_wind {
+        anyhow::bail!(
+            "Rider min_wind ({}) must be non-negative and below max_wind ({})",
+            rider.min_wind,
+            rider.max_wind
+        );
+    }
+    if rider.max_gust_factor.is_nan() || rider.max_gust_factor < 1.0 {
+        anyhow::bail!(
+            "Rider max_gust_factor must be at least 1.0 (got {})",
+            rider.max_gust_factor
+        );
+    }
+    for direction in &rider.allowed_directions {
+        direction
+            .parse::<ShoreWind>()
+            .context("Invalid direction in [rider] allowed_directions")?;
+    }
+    if let Some(weight) = rider.weight {
+        if weight.is_nan() || weight <= 0.0 {
+            anyhow::bail!("Rider weight must be positive (got {})", weight);
+        }
+    }
+    if let Some(sail) = rider.sails.iter().find(|s| s.is_nan() || **s <= 0.0) {
+        anyhow::bail!("Rider sail sizes must be positive (got {})", sail);
+    }
+    if let Some(board) = rider.boards.iter().find(|b| b.volume.is_nan() || b.volume <= 0.0) {
+        anyhow::bail!(
+            "Volume of board '{}' must be positive (got {})",
+            board.name,
+            board.volume
+        );
+    }
+
+    Ok(())
+}
+
+/// Resolve the response cache from the `[cache]` section and the
+/// `--no-cache` / `--refresh` / `--offline` flags
+///
+/// Without a configured `dir` the cache lives under the XDG cache directory;
+/// if there is none, caching is disabled (and offline runs find nothing).
+pub fn resolve_cache_settings(args: &Args, cache: &CacheConfig) -> CacheSettings {
+    let dir = cache
+        .dir
+        .clone()
+        .or_else(|| dirs::cache_dir().map(|dir| dir.join("windsurf-forecast")));
+
+    let mode = if args.offline {
+        CacheMode::Offline
+    } else if args.no_cache {
+        CacheMode::Disabled
+    } else if args.refresh {
+        CacheMode::Refresh
+    } else {
+        CacheMode::ReadWrite
+    };
+
+    match dir {
+        Some(dir) => CacheSettings {
+            dir,
+            ttl: std::time::Duration::from_secs(cache.ttl_minutes.saturating_mul(60)),
+            mode,
+        },
+        None if args.offline => CacheSettings {
+            mode: CacheMode::Offline,
+            ..CacheSettings::disabled()
+        },
+        None => CacheSettings::disabled(),
+    }
+}
+
+/// Resolve `--record` / `--replay` (clap rejects both at once)
+pub fn resolve_fixture_mode(args: &Args) -> FixtureMode {
+    match (&args.record, &args.replay) {
+        (_, Some(dir)) => FixtureMode::Replay(dir.clone()),
+        (Some(dir), None) => FixtureMode::Record(dir.clone()),
+        (None, None) => FixtureMode::Off,
+    }
+}
+
+/// Resolve the timeout and retries of provider requests from the `[http]` section
+pub fn resolve_retry_policy(http: &HttpConfig) -> Result<RetryPolicy> {
+    if http.max_attempts == 0 {
+        anyhow::bail!("[http] max_attempts must be at least 1 (1 disables retries)");
+    }
+    if http.timeout_seconds == 0 {
+        anyhow::bail!("[http] timeout_seconds must be positive");
+    }
+    Ok(RetryPolicy {
+        max_attempts: http.max_attempts,
+        timeout: std::time::Duration::from_secs(http.timeout_seconds),
+        ..RetryPolicy::default()
+    })
+}
+
+/// Resolve API base URL overrides (Environment > Config)
+///
+/// Keys of the `[endpoints]` section must name a provider with an API of its
+/// own; each provider's `base_url_var` (e.g. `STORMGLASS_API_URL`) overrides
+/// the configured value. URLs must be http(s); a trailing slash is dropped so
+/// providers can append their paths.
+pub fn resolve_endpoints(endpoints: &BTreeMap<String, String>) -> Result<BTreeMap<String, String>> {
+    let configurable: Vec<_> = crate::provider_registry::all_provider_names()
+        .filter_map(crate::provider_registry::get_provider_metadata)
+        .filter(|meta| meta.base_url.is_some())
+        .collect();
+
+    let mut resolved = BTreeMap::new();
+    for (provider, url) in endpoints {
+        if !configurable.iter().any(|meta| meta.name == provider) {
+            let names: Vec<_> = configurable.iter().map(|meta| meta.name).collect();
+            anyhow::bail!(
+                "Unknown provider '{}' in [endpoints]. Providers with an API endpoint: {}",
+                provider,
+                names.join(", ")
+            );
+        }
+        let url = parse_base_url(url)
+            .with_context(|| format!("Invalid [endpoints] URL for '{}'", provider))?;
+        resolved.insert(provider.clone(), url);
+    }
+
+    for meta in &configurable {
+        let Some(var) = meta.base_url_var else { continue };
+        if let Some(url) = std::env::var(var).ok().filter(|url| !url.is_empty()) {
+            let url = parse_base_url(&url).with_context(|| format!("Invalid {}", var))?;
+            resolved.insert(meta.name.to_string(), url);
+        }
+    }
+
+    Ok(resolved)
+}
+
+fn parse_base_url(url: &str) -> Result<String> {
+    let parsed = reqwest::Url::parse(url).with_context(|| format!("'{}' is not a URL", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!("'{}' must be an http or https URL", url);
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Resolve output units with precedence (CLI > Config > Default) and parse them
+///
+/// Unit names are case-insensitive; see [`crate::units`] for accepted spellings.
+pub fn resolve_output_units(args: &Args, config: &Config) -> Result<OutputUnits> {
+    let mut units = OutputUnits::default();
+
+    if let Some(name) = args.wind_unit.as_ref().or(config.general.wind_unit.as_ref()) {
+        units.wind = name.parse().context("Invalid wind unit")?;
+    }
+    if let Some(name) = args.temp_unit.as_ref().or(config.general.temp_unit.as_ref()) {
+        units.temperature = name.parse().context("Invalid temperature unit")?;
+    }
+    if let Some(name) = args.height_unit.as_ref().or(config.general.height_unit.as_ref()) {
+        units.height = name.parse().context("Invalid height unit")?;
+    }
+
+    Ok(units)
+}
+
 pub fn resolve_from_args_and_file(args: &Args) -> Result<ResolvedConfig> {
+    if args.is_multi_spot() {
+        anyhow::bail!("Several spots requested; use resolve_spots_from_args_and_file()");
+    }
     let config = load_config_from_file(args.config_file_path.as_ref())?;
-    
+    resolve_with_spot(args, config, args.spot.first().map(String::as_str))
+}
+
+/// Resolve one configuration per requested spot (`--all-spots` or `--spot a,b,c`)
+///
+/// `--all-spots` takes every `[spots]` entry in name order. Any invalid spot
+/// fails the whole resolution before anything is fetched.
+pub fn resolve_spots_from_args_and_file(args: &Args) -> Result<Vec<ResolvedConfig>> {
+    let config = load_config_from_file(args.config_file_path.as_ref())?;
+
+    let names: Vec<String> = if args.all_spots {
+        config.spots.keys().cloned().collect()
+    } else {
+        args.spot.clone()
+    };
+    if names.is_empty() {
+        anyhow::bail!(
+            "No spots to fetch. Add [spots.<name>] sections with lat and lng to the config file."
+        );
+    }
+
+    names
+        .iter()
+        .map(|name| resolve_with_spot(args, config.clone(), Some(name)))
+        .collect()
+}
+
+/// Directory of the config file, which relative paths in it refer to
+fn config_dir(args: &Args) -> Result<PathBuf> {
+    let path = match &args.config_file_path {
+        Some(path) => path.clone(),
+        None => get_default_config_path()?,
+    };
+    Ok(path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+fn resolve_with_spot(args: &Args, config: Config, spot_name: Option<&str>) -> Result<ResolvedConfig> {
+    let spot = spot_name
+        .map(|name| resolve_spot(name, &config, &config_dir(args)?))
+        .transpose()?;
+    let spot_config = spot.as_ref().map(|s| &s.config);
+
     let timezone_config = TimezoneConfig::load_with_precedence(
         args.timezone.as_deref(),
-        Some(&config.general.timezone),
+        spot_config
+            .and_then(|s| s.timezone.as_deref())
+            .or(Some(&config.general.timezone)),
     )?;
     
     timezone_config.display_timezone_warning_if_default();
     
-    let (lat, lng) = resolve_coordinates(
-        args.lat,
-        args.lng,
-        &config,
-    )?;
+    let (lat, lng) = match spot_config {
+        Some(s) => (s.lat, s.lng),
+        None => resolve_coordinates(args.lat, args.lng, &config)?,
+    };
     
     let days_ahead = args.days_ahead;
     let first_day_offset = args.first_day_offset;
     
     validate_date_range(days_ahead, first_day_offset)?;
     
-    crate::provider_registry::validate_provider_name(&args.provider)?;
+    // Like the timezone, a `default_provider` equal to the built-in one
+    // counts as not configured
+    let (provider, provider_source) = resolve_with_source(
+        args.provider.clone(),
+        spot_config.and_then(|s| s.provider.clone()).or_else(|| {
+            Some(config.general.default_provider.clone()).filter(|p| *p != default_provider())
+        }),
+        default_provider(),
+    );
+    crate::provider_registry::validate_provider_name(&provider)?;
+
+    if provider == "composite" {
+        validate_composite_config(&config.composite)?;
+    }
+
+    if args.ensemble {
+        validate_ensemble_config(&config.ensemble)?;
+    }
+
+    validate_rider_config(&config.rider)?;
+
+    let units = resolve_output_units(args, &config)?;
     
     Ok(ResolvedConfig {
-        provider: args.provider.clone(),
+        provider,
+        provider_source,
+        spot,
         timezone: timezone_config.timezone,
         lat,
         lng,
         days_ahead,
         first_day_offset,
+        units,
+        composite: config.composite,
+        ensemble: config.ensemble,
+        rider: config.rider,
+        tides: args.tides,
+        cache: resolve_cache_settings(args, &config.cache),
+        fixtures: resolve_fixture_mode(args),
+        retry: resolve_retry_policy(&config.http)?,
+        endpoints: resolve_endpoints(&config.endpoints)?,
     })
 }
\ No newline at end of file
@@ -62,8 +62,14 @@
 //! ```
 
 use chrono_tz::Tz;
+use std::collections::BTreeMap;
 use std::fmt;
 
+use super::loader::{CompositeConfig, EnsembleConfig, RiderConfig, SpotConfig};
+use crate::http::{CacheSettings, FixtureMode, RetryPolicy};
+use crate::tide::TideTable;
+use crate::units::OutputUnits;
+
 /// Final validated configuration containing all resolved values
 ///
 /// All fields are populated (no `Option<T>`) and have been validated according to
@@ -73,6 +79,13 @@ use std::fmt;
 pub struct ResolvedConfig {
     /// Weather provider name (validated against registry)
     pub provider: String,
+
+    /// Where `provider` came from: `--provider`, the config file (the
+    /// spot's `provider` or `[general] default_provider`) or the default
+    pub provider_source: ConfigSource,
+
+    /// Spot selected with `--spot` (coordinates above come from it)
+    pub spot: Option<ResolvedSpot>,
     
     /// Target timezone for output timestamps
     pub timezone: Tz,
@@ -88,19 +101,81 @@ pub struct ResolvedConfig {
     
     /// Offset for forecast start date (validated: 0-7)
     pub first_day_offset: i32,
+
+    /// Units all output values are converted to
+    pub units: OutputUnits,
+
+    /// Field → provider mapping (validated only when provider is "composite")
+    pub composite: CompositeConfig,
+
+    /// Ensemble members and thresholds (validated only with --ensemble)
+    pub ensemble: EnsembleConfig,
+
+    /// Thresholds for hour scoring and session windows
+    pub rider: RiderConfig,
+
+    /// Whether providers that support it should fetch tide data
+    pub tides: bool,
+
+    /// Where and how long provider responses are cached
+    pub cache: CacheSettings,
+
+    /// Whether provider exchanges are recorded or replayed
+    pub fixtures: FixtureMode,
+
+    /// Timeout and retries of provider requests
+    pub retry: RetryPolicy,
+
+    /// API base URL overrides by provider name, from the environment or
+    /// the `[endpoints]` section
+    pub endpoints: BTreeMap<String, String>,
+}
+
+impl ResolvedConfig {
+    /// Base URL override for `provider`, if any
+    pub fn endpoint(&self, provider: &str) -> Option<&str> {
+        self.endpoints.get(provider).map(String::as_str)
+    }
 }
 
 impl fmt::Display for ResolvedConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "📋 Configuration:")?;
         writeln!(f, "   Provider: {}", self.provider)?;
+        if self.provider == "composite" {
+            for (field, provider) in self.composite.entries() {
+                if let Some(provider) = provider {
+                    writeln!(f, "     {} ← {}", field, provider)?;
+                }
+            }
+        }
+        for (provider, url) in &self.endpoints {
+            writeln!(f, "   Endpoint: {} → {}", provider, url)?;
+        }
+        if let Some(spot) = &self.spot {
+            writeln!(f, "   Spot: {}", spot.name)?;
+            if let Some(notes) = &spot.config.notes {
+                writeln!(f, "     Notes: {}", notes)?;
+            }
+        }
         writeln!(f, "   Days ahead: {}", self.days_ahead)?;
         writeln!(f, "   First day offset: {}", self.first_day_offset)?;
         writeln!(f, "   Timezone: {}", self.timezone.name())?;
+        writeln!(f, "   Units: {}", self.units)?;
         write!(f, "   Coordinates: ({:.6}, {:.6})", self.lat, self.lng)
     }
 }
 
+/// A spot from the `[spots]` table, together with its name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSpot {
+    pub name: String,
+    pub config: SpotConfig,
+
+    /// Harmonic constants loaded from `config.tide_constituents`
+    pub tide_table: Option<TideTable>,
+}
+
 /// Raw input sources before precedence resolution
 ///
 /// `Option<T>` values enable source tracking for better error messages.
@@ -0,0 +1,270 @@
+//! Multi-provider ensemble statistics
+//!
+//! Runs of several providers for the same window are aligned by UTC hour and
+//! reduced to per-hour mean/min/max/standard deviation of wind speed, gust
+//! and swell height. Hours where the providers' spread (max − min) exceeds
+//! the configured thresholds are flagged as disagreements.
+//!
+//! Disagreement is judged in m/s and meters, the units the thresholds are
+//! configured in. The reported statistics are computed in the selected
+//! output units.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::config::loader::EnsembleConfig;
+use crate::forecast_provider::{convert_timezone, LocalTimestamp, UtcTimestamp, WeatherData};
+use crate::units::{Length, LengthUnit, OutputUnits, Quantity, Speed, SpeedUnit, Unit};
+
+/// Summary statistics of one quantity across ensemble members
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EnsembleStats {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Population standard deviation
+    #[serde(rename = "stdDev")]
+    pub std_dev: f64,
+    /// Number of providers that reported a value
+    pub count: usize,
+}
+
+impl EnsembleStats {
+    /// Returns `None` when no member reported a value
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(Self {
+            mean,
+            min,
+            max,
+            std_dev: variance.sqrt(),
+            count: values.len(),
+        })
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+/// Ensemble statistics for a single hour
+#[derive(Debug, Clone, Serialize)]
+pub struct EnsembleHour {
+    pub time: LocalTimestamp,
+
+    /// Providers that returned data for this hour
+    pub providers: Vec<String>,
+
+    #[serde(rename = "windSpeed", skip_serializing_if = "Option::is_none")]
+    pub wind_speed: Option<EnsembleStats>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gust: Option<EnsembleStats>,
+
+    #[serde(rename = "swellHeight", skip_serializing_if = "Option::is_none")]
+    pub swell_height: Option<EnsembleStats>,
+
+    /// Quantities whose spread exceeds the configured threshold
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disagreement: Vec<&'static str>,
+}
+
+#[derive(Default)]
+struct HourSamples {
+    providers: Vec<String>,
+    wind_speed: Vec<Speed>,
+    gust: Vec<Speed>,
+    swell_height: Vec<Length>,
+}
+
+/// Statistics of `samples` in `unit` and the spread in `base`
+fn stats_and_spread<U: Unit>(
+    samples: &[Quantity<U>],
+    unit: U,
+    base: U,
+) -> (Option<EnsembleStats>, f64) {
+    let values = |u: U| samples.iter().map(|q| q.to(u).value()).collect::<Vec<_>>();
+    let spread = EnsembleStats::from_values(&values(base)).map_or(0.0, |s| s.spread());
+    (EnsembleStats::from_values(&values(unit)), spread)
+}
+
+/// Align members by UTC hour within `[start, end]` and compute statistics
+pub fn build_ensemble(
+    members: &[(String, WeatherData)],
+    config: &EnsembleConfig,
+    units: &OutputUnits,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    target_tz: Tz,
+) -> Result<Vec<EnsembleHour>> {
+    let mut samples: BTreeMap<DateTime<Utc>, HourSamples> = BTreeMap::new();
+
+    for (provider, data) in members {
+        for point in &data.data_points {
+            let utc = point.time.utc();
+            if utc < start || utc > end {
+                continue;
+            }
+            let hour = samples.entry(utc).or_default();
+            hour.providers.push(provider.clone());
+            hour.wind_speed.extend(point.wind.wind_speed);
+            hour.gust.extend(point.wind.gust);
+            hour.swell_height.extend(point.waves.swell_height);
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(utc, hour)| {
+            let ms = SpeedUnit::MetersPerSecond;
+            let (wind_speed, wind_spread) = stats_and_spread(&hour.wind_speed, units.wind, ms);
+            let (gust, gust_spread) = stats_and_spread(&hour.gust, units.wind, ms);
+            let (swell_height, swell_spread) =
+                stats_and_spread(&hour.swell_height, units.height, LengthUnit::Meters);
+
+            let mut disagreement = Vec::new();
+            for (name, spread, threshold) in [
+                ("windSpeed", wind_spread, config.wind_speed_spread),
+                ("gust", gust_spread, config.gust_spread),
+                ("swellHeight", swell_spread, config.swell_height_spread),
+            ] {
+                if spread > threshold {
+                    disagreement.push(name);
+                }
+            }
+
+            Ok(EnsembleHour {
+                time: convert_timezone(UtcTimestamp(utc), target_tz)?,
+                providers: hour.providers,
+                wind_speed,
+                gust,
+                swell_height,
+                disagreement,
+            })
+        })
+        .collect()
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::WeatherDataPoint;
+    use crate::units::MS_TO_KNOTS;
+
+    fn member(
+        provider: &str,
+        wind_unit: SpeedUnit,
+        hours: &[(i64, f64, Option<f64>)],
+    ) -> (String, WeatherData) {
+        let data_points = hours
+            .iter()
+            .map(|&(ts, wind, swell)| {
+                let utc = UtcTimestamp(DateTime::<Utc>::from_timestamp(ts, 0).unwrap());
+                let mut point = WeatherDataPoint::empty(convert_timezone(utc, chrono_tz::UTC).unwrap());
+                point.wind.wind_speed = Some(Speed::new(wind, wind_unit));
+                point.waves.swell_height = swell.map(|h| Length::new(h, LengthUnit::Meters));
+                point
+            })
+            .collect();
+        (provider.to_string(), WeatherData { data_points, alerts: None, tides: Vec::new() })
+    }
+
+    #[test]
+    fn test_stats_from_values() {
+        let stats = EnsembleStats::from_values(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_eq!(stats.std_dev, 2.0);
+        assert_eq!(stats.count, 8);
+        assert!(EnsembleStats::from_values(&[]).is_none());
+    }
+
+    #[test]
+    fn test_build_ensemble_normalizes_and_flags_disagreement() {
+        let members = vec![
+            // 10 m/s expressed in knots
+            member(
+                "stormglass",
+                SpeedUnit::Knots,
+                &[(0, 10.0 * MS_TO_KNOTS, Some(1.0)), (3600, 19.4384, None)],
+            ),
+            member(
+                "openmeteo",
+                SpeedUnit::MetersPerSecond,
+                &[(0, 10.0, Some(1.2)), (3600, 4.0, None)],
+            ),
+            member("windy", SpeedUnit::MetersPerSecond, &[(7200, 8.0, None)]),
+        ];
+        let start = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+        let end = DateTime::<Utc>::from_timestamp(3600, 0).unwrap();
+
+        let units = OutputUnits {
+            wind: SpeedUnit::MetersPerSecond,
+            ..Default::default()
+        };
+        let hours = build_ensemble(
+            &members,
+            &EnsembleConfig::default(),
+            &units,
+            start,
+            end,
+            chrono_tz::UTC,
+        )
+        .unwrap();
+
+        assert_eq!(hours.len(), 2, "hour outside the window is dropped");
+
+        let first = &hours[0];
+        let wind = first.wind_speed.as_ref().unwrap();
+        assert!((wind.mean - 10.0).abs() < 1e-9);
+        assert_eq!(wind.count, 2);
+        assert!(first.disagreement.is_empty());
+        assert_eq!(first.swell_height.as_ref().unwrap().count, 2);
+
+        let second = &hours[1];
+        assert_eq!(second.providers, vec!["stormglass", "openmeteo"]);
+        assert_eq!(second.disagreement, vec!["windSpeed"]);
+        assert!(second.swell_height.is_none());
+    }
+
+    #[test]
+    fn test_stats_use_output_units_but_thresholds_stay_in_ms() {
+        let members = vec![
+            member("openmeteo", SpeedUnit::MetersPerSecond, &[(0, 10.0, None)]),
+            member("windy", SpeedUnit::MetersPerSecond, &[(0, 12.5, None)]),
+        ];
+        let at = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+
+        let hours = build_ensemble(
+            &members,
+            &EnsembleConfig::default(),
+            &OutputUnits::default(),
+            at,
+            at,
+            chrono_tz::UTC,
+        )
+        .unwrap();
+
+        let wind = hours[0].wind_speed.as_ref().unwrap();
+        assert!((wind.min - 10.0 * MS_TO_KNOTS).abs() < 1e-9, "stats are in knots");
+        assert!(
+            hours[0].disagreement.is_empty(),
+            "2.5 m/s spread is below the 3.0 m/s threshold even though it is ~4.9 knots"
+        );
+    }
+}
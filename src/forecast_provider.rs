@@ -4,6 +4,11 @@ use chrono::{DateTime, TimeZone, Utc};
 use chrono_tz::Tz;
 use serde::{Serialize, Serializer};
 
+use crate::gear::GearRecommendation;
+use crate::sessions::HourScore;
+use crate::shore::ShoreWind;
+use crate::units::{Angle, Length, OutputUnits, Period, Speed, Temperature};
+
 // ============================================================================
 // Newtype Wrappers for Timezone Safety
 // ============================================================================
@@ -31,7 +36,7 @@ impl UtcTimestamp {
 /// Used in output structures after conversion to user's target timezone.
 /// This type makes it explicit that a timestamp has been converted and is
 /// ready for display/serialization.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LocalTimestamp {
     inner: DateTime<Tz>,
 }
@@ -41,6 +46,16 @@ impl LocalTimestamp {
     pub fn new(dt: DateTime<Tz>) -> Self {
         Self { inner: dt }
     }
+
+    /// The same instant in UTC, used to match data points across providers
+    pub fn utc(&self) -> DateTime<Utc> {
+        self.inner.with_timezone(&Utc)
+    }
+
+    /// The timestamp in its target timezone
+    pub fn local(&self) -> DateTime<Tz> {
+        self.inner
+    }
 }
 
 // Custom serialization to maintain "YYYY-MM-DD HH:MM" format
@@ -74,7 +89,77 @@ pub fn convert_timezone(utc_timestamp: UtcTimestamp, target_tz: Tz) -> Result<Lo
 pub struct WeatherData {
     pub data_points: Vec<WeatherDataPoint>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub alerts: Option<Vec<String>>,
+    pub alerts: Option<Vec<WeatherAlert>>,
+
+    /// High and low tides within the forecast range (only from providers
+    /// asked for tide data)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tides: Vec<TideEvent>,
+}
+
+/// How serious a weather alert is, from the least to the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Advisory,
+    Watch,
+    Warning,
+}
+
+impl AlertSeverity {
+    /// Severity named in an event title such as "Gale Warning" or "Small
+    /// Craft Advisory"; `None` when the title names none
+    pub fn from_event(event: &str) -> Option<Self> {
+        let event = event.to_lowercase();
+        if event.contains("warning") {
+            Some(AlertSeverity::Warning)
+        } else if event.contains("watch") {
+            Some(AlertSeverity::Watch)
+        } else if event.contains("advisory") {
+            Some(AlertSeverity::Advisory)
+        } else {
+            None
+        }
+    }
+}
+
+/// An official weather alert issued for the forecast location
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeatherAlert {
+    /// Agency that issued the alert
+    pub sender: String,
+
+    /// Alert title, e.g. "Gale Warning"
+    pub event: String,
+
+    pub start: LocalTimestamp,
+
+    pub end: LocalTimestamp,
+
+    pub description: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<AlertSeverity>,
+}
+
+/// Whether a tide extreme is a high or a low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TideKind {
+    High,
+    Low,
+}
+
+/// A high or low tide
+#[derive(Debug, Clone, Serialize)]
+pub struct TideEvent {
+    pub time: LocalTimestamp,
+
+    /// Sea level relative to mean sea level
+    pub height: Length,
+
+    #[serde(rename = "type")]
+    pub kind: TideKind,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -82,22 +167,114 @@ pub struct WeatherDataPoint {
     pub time: LocalTimestamp,
 
     #[serde(rename = "airTemperature", skip_serializing_if = "Option::is_none")]
-    pub air_temperature: Option<f64>,
+    pub air_temperature: Option<Temperature>,
 
     pub wind: WindDatapoinSection,
 
     pub waves: WaveDatapointSection,
 
     #[serde(rename = "waterTemperature", skip_serializing_if = "Option::is_none")]
-    pub water_temperature: Option<f64>,
+    pub water_temperature: Option<Temperature>,
 
     pub clouds: CloudDatapointSection,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub precipitation: Option<f64>,
+
+    /// Sea level relative to mean sea level
+    #[serde(rename = "tideHeight", skip_serializing_if = "Option::is_none")]
+    pub tide_height: Option<Length>,
+
+    /// Whether the sun is up, from the offline sunrise/sunset computation
+    #[serde(rename = "isDaylight", skip_serializing_if = "Option::is_none")]
+    pub is_daylight: Option<bool>,
+
+    /// Sailability rating against the `[rider]` thresholds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sailability: Option<HourScore>,
+
+    /// Recommended sail and board (only with a `[rider]` weight)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gear: Option<GearRecommendation>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl WeatherDataPoint {
+    /// A data point for `time` with every field unset
+    pub fn empty(time: LocalTimestamp) -> Self {
+        Self {
+            time,
+            air_temperature: None,
+            wind: WindDatapoinSection::default(),
+            waves: WaveDatapointSection::default(),
+            water_temperature: None,
+            clouds: CloudDatapointSection::default(),
+            precipitation: None,
+            tide_height: None,
+            is_daylight: None,
+            sailability: None,
+            gear: None,
+        }
+    }
+
+    /// Fill every field that is `None` in `self` with the value from `other`
+    ///
+    /// Used to combine providers that cover different sections of the same
+    /// hour (e.g. marine waves + atmospheric wind). Values already present in
+    /// `self` always win.
+    pub fn fill_missing_from(&mut self, other: &WeatherDataPoint) {
+        fn fill<T: Copy>(target: &mut Option<T>, source: Option<T>) {
+            if target.is_none() {
+                *target = source;
+            }
+        }
+
+        fill(&mut self.air_temperature, other.air_temperature);
+        fill(&mut self.wind.wind_speed, other.wind.wind_speed);
+        fill(&mut self.wind.wind_direction, other.wind.wind_direction);
+        fill(&mut self.wind.gust, other.wind.gust);
+        fill(&mut self.waves.swell_height, other.waves.swell_height);
+        fill(&mut self.waves.swell_period, other.waves.swell_period);
+        fill(&mut self.waves.swell_direction, other.waves.swell_direction);
+        fill(&mut self.waves.wind_wave_height, other.waves.wind_wave_height);
+        fill(&mut self.waves.wind_wave_period, other.waves.wind_wave_period);
+        fill(&mut self.waves.wind_wave_direction, other.waves.wind_wave_direction);
+        fill(&mut self.water_temperature, other.water_temperature);
+        fill(&mut self.clouds.cloud_cover, other.clouds.cloud_cover);
+        fill(&mut self.clouds.low_cloud_cover, other.clouds.low_cloud_cover);
+        fill(&mut self.clouds.medium_cloud_cover, other.clouds.medium_cloud_cover);
+        fill(&mut self.clouds.high_cloud_cover, other.clouds.high_cloud_cover);
+        fill(&mut self.precipitation, other.precipitation);
+        fill(&mut self.tide_height, other.tide_height);
+    }
+
+    /// Convert every typed value to the selected output units
+    pub fn convert_units(&mut self, units: &OutputUnits) {
+        let speed = |v: &mut Option<Speed>| *v = v.map(|q| q.to(units.wind));
+        speed(&mut self.wind.wind_speed);
+        speed(&mut self.wind.gust);
+
+        let length = |v: &mut Option<Length>| *v = v.map(|q| q.to(units.height));
+        length(&mut self.waves.swell_height);
+        length(&mut self.waves.wind_wave_height);
+        length(&mut self.tide_height);
+
+        let temperature = |v: &mut Option<Temperature>| *v = v.map(|q| q.to(units.temperature));
+        temperature(&mut self.air_temperature);
+        temperature(&mut self.water_temperature);
+    }
+
+    /// Classify the wind direction against a shore facing `shore_bearing`
+    ///
+    /// Hours without a wind direction are left unclassified.
+    pub fn classify_shore_wind(&mut self, shore_bearing: f64) {
+        self.wind.shore_wind = self
+            .wind
+            .wind_direction
+            .map(|direction| ShoreWind::classify(direction.value(), shore_bearing));
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CloudDatapointSection {
     #[serde(rename = "cloudsCover", skip_serializing_if = "Option::is_none")]
     pub cloud_cover: Option<f64>,
@@ -112,37 +289,42 @@ pub struct CloudDatapointSection {
     pub high_cloud_cover: Option<f64>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct WaveDatapointSection {
     #[serde(rename = "swellHeight", skip_serializing_if = "Option::is_none")]
-    pub swell_height: Option<f64>,
+    pub swell_height: Option<Length>,
 
     #[serde(rename = "swellPeriod", skip_serializing_if = "Option::is_none")]
-    pub swell_period: Option<f64>,
+    pub swell_period: Option<Period>,
 
     #[serde(rename = "swellDirection", skip_serializing_if = "Option::is_none")]
-    pub swell_direction: Option<f64>,
+    pub swell_direction: Option<Angle>,
 
     #[serde(rename = "windWaveHeight", skip_serializing_if = "Option::is_none")]
-    pub wind_wave_height: Option<f64>,
+    pub wind_wave_height: Option<Length>,
 
     #[serde(rename = "windWavePeriod", skip_serializing_if = "Option::is_none")]
-    pub wind_wave_period: Option<f64>,
+    pub wind_wave_period: Option<Period>,
 
     #[serde(rename = "windWaveDirection", skip_serializing_if = "Option::is_none")]
-    pub wind_wave_direction: Option<f64>,
+    pub wind_wave_direction: Option<Angle>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct WindDatapoinSection {
     #[serde(rename = "windSpeed", skip_serializing_if = "Option::is_none")]
-    pub wind_speed: Option<f64>,
+    pub wind_speed: Option<Speed>,
 
     #[serde(rename = "windDirection", skip_serializing_if = "Option::is_none")]
-    pub wind_direction: Option<f64>,
+    pub wind_direction: Option<Angle>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gust: Option<f64>,
+    pub gust: Option<Speed>,
+
+    /// Wind direction relative to the spot's shoreline (only for spots with
+    /// a `shore_bearing`)
+    #[serde(rename = "shoreWind", skip_serializing_if = "Option::is_none")]
+    pub shore_wind: Option<ShoreWind>,
 }
 
 /// Trait that all weather forecast providers must implement
@@ -155,9 +337,18 @@ pub trait ForecastProvider: Send + Sync {
 
     /// Get the API key from environment variables
     /// Returns the API key value or an error if not found/invalid
+    ///
+    /// Keyless providers (registered with `api_key_var: None`) keep the
+    /// default implementation and never call it.
     fn get_api_key() -> Result<String>
     where
-        Self: Sized;
+        Self: Sized,
+    {
+        Err(anyhow!(
+            "{} does not use an API key",
+            std::any::type_name::<Self>()
+        ))
+    }
 
     /// Fetch weather data for the given time range and location
     /// Returns a vector of weather data points
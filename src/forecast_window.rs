@@ -0,0 +1,147 @@
+//! Forecast window aligned to local days
+//!
+//! A forecast "day" is a calendar day in the target timezone, not in UTC:
+//! for Asia/Jerusalem the first day of a winter forecast starts at 22:00 UTC
+//! the evening before. Days around DST transitions are 23 or 25 hours long.
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::forecast_provider::WeatherData;
+
+/// Inclusive UTC range covering whole local days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ForecastWindow {
+    /// Window from local midnight `first_day_offset` days after `now`'s local
+    /// date, spanning `days_ahead` local days
+    pub fn local_days(now: DateTime<Utc>, first_day_offset: u32, days_ahead: u32, tz: Tz) -> Self {
+        let today = now.with_timezone(&tz).date_naive();
+        let first = today + Days::new(first_day_offset as u64);
+        let after_last = first + Days::new(days_ahead as u64);
+
+        Self {
+            start: local_midnight(first, tz),
+            // Last second of the final day, matching the previous 23:59:59 end
+            end: local_midnight(after_last, tz) - chrono::Duration::seconds(1),
+        }
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start <= time && time <= self.end
+    }
+
+    /// Local calendar dates covered by the window, in order
+    pub fn local_dates(&self, tz: Tz) -> Vec<NaiveDate> {
+        let last = self.end.with_timezone(&tz).date_naive();
+        self.start
+            .with_timezone(&tz)
+            .date_naive()
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .collect()
+    }
+
+    /// Drop data points and tide events outside the window
+    ///
+    /// Some providers ignore the requested range and return everything they
+    /// have (OpenWeatherMap: 48 hours from now; Windy: the whole model run).
+    pub fn trim(&self, data: &mut WeatherData) {
+        data.data_points.retain(|point| self.contains(point.time.utc()));
+        data.tides.retain(|tide| self.contains(tide.time.utc()));
+    }
+}
+
+/// First instant of `date` in `tz`
+///
+/// Where DST starts at midnight the local day begins at the first minute
+/// after the gap (e.g. 01:00); where a fold repeats midnight the earlier
+/// instant is used.
+fn local_midnight(date: NaiveDate, tz: Tz) -> DateTime<Utc> {
+    let mut time = date.and_time(NaiveTime::MIN);
+    loop {
+        if let Some(local) = tz.from_local_datetime(&time).earliest() {
+            return local.with_timezone(&Utc);
+        }
+        time += chrono::Duration::minutes(1);
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::{convert_timezone, UtcTimestamp, WeatherDataPoint};
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_window_starts_at_local_midnight() {
+        let jerusalem: Tz = "Asia/Jerusalem".parse().unwrap();
+        // 23:30 UTC on Dec 7 is already Dec 8 in Jerusalem (UTC+2)
+        let window = ForecastWindow::local_days(utc("2025-12-07T23:30:00Z"), 0, 2, jerusalem);
+        assert_eq!(window.start, utc("2025-12-07T22:00:00Z"));
+        assert_eq!(window.end, utc("2025-12-09T21:59:59Z"));
+
+        let los_angeles: Tz = "America/Los_Angeles".parse().unwrap();
+        let window = ForecastWindow::local_days(utc("2025-07-01T12:00:00Z"), 1, 1, los_angeles);
+        assert_eq!(window.start, utc("2025-07-02T07:00:00Z"), "PDT is UTC-7");
+        assert_eq!(window.end, utc("2025-07-03T06:59:59Z"));
+    }
+
+    #[test]
+    fn test_local_dates_cover_each_day() {
+        let jerusalem: Tz = "Asia/Jerusalem".parse().unwrap();
+        let window = ForecastWindow::local_days(utc("2025-12-07T23:30:00Z"), 0, 2, jerusalem);
+        let dates: Vec<String> = window.local_dates(jerusalem).iter().map(|d| d.to_string()).collect();
+        assert_eq!(dates, vec!["2025-12-08", "2025-12-09"]);
+    }
+
+    #[test]
+    fn test_dst_days_are_23_and_25_hours_long() {
+        let new_york: Tz = "America/New_York".parse().unwrap();
+
+        let spring = ForecastWindow::local_days(utc("2025-03-09T12:00:00Z"), 0, 1, new_york);
+        assert_eq!(spring.start, utc("2025-03-09T05:00:00Z"));
+        assert_eq!((spring.end - spring.start).num_seconds(), 23 * 3600 - 1);
+
+        let autumn = ForecastWindow::local_days(utc("2025-11-02T12:00:00Z"), 0, 1, new_york);
+        assert_eq!((autumn.end - autumn.start).num_seconds(), 25 * 3600 - 1);
+    }
+
+    #[test]
+    fn test_day_starting_in_dst_gap_begins_after_the_gap() {
+        // Chile moves clocks from 00:00 to 01:00 on the first Sunday of September
+        let santiago: Tz = "America/Santiago".parse().unwrap();
+        let window = ForecastWindow::local_days(utc("2025-09-07T12:00:00Z"), 0, 1, santiago);
+        assert_eq!(window.start, utc("2025-09-07T04:00:00Z"));
+    }
+
+    #[test]
+    fn test_trim_drops_points_outside_window() {
+        let window = ForecastWindow {
+            start: utc("2025-12-07T22:00:00Z"),
+            end: utc("2025-12-08T21:59:59Z"),
+        };
+        let data_points = ["2025-12-07T21:00:00Z", "2025-12-07T22:00:00Z", "2025-12-08T22:00:00Z"]
+            .iter()
+            .map(|s| {
+                let time = convert_timezone(UtcTimestamp(utc(s)), chrono_tz::UTC).unwrap();
+                WeatherDataPoint::empty(time)
+            })
+            .collect();
+        let mut data = WeatherData { data_points, alerts: None, tides: Vec::new() };
+
+        window.trim(&mut data);
+
+        assert_eq!(data.data_points.len(), 1);
+        assert_eq!(data.data_points[0].time.utc(), window.start);
+    }
+}
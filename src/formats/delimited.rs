@@ -0,0 +1,201 @@
+//! CSV and TSV rendering of the hourly data
+//!
+//! One row per hour. Nested sections are flattened into dotted column names
+//! that follow the JSON keys (`wind.windSpeed`, `waves.swellHeight`, ...),
+//! and typed values carry their unit in the header, e.g. `wind.gust [knots]`.
+//! Columns without a value in any hour are left out, as in the JSON output.
+//!
+//! CSV fields containing the separator, quotes or line breaks are quoted;
+//! TSV has no quoting, so tabs and line breaks are replaced by spaces.
+
+use crate::forecast_provider::WeatherDataPoint;
+use crate::units::{Quantity, Unit};
+
+struct Column {
+    header: String,
+    values: Vec<Option<String>>,
+}
+
+impl Column {
+    fn new(header: &str, unit: Option<&str>, values: Vec<Option<String>>) -> Self {
+        let header = match unit {
+            Some(unit) => format!("{} [{}]", header, unit),
+            None => header.to_string(),
+        };
+        Self { header, values }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+}
+
+/// Column of a typed value; the unit comes from the first value present
+fn quantity<U: Unit>(
+    hours: &[WeatherDataPoint],
+    header: &str,
+    field: impl Fn(&WeatherDataPoint) -> Option<Quantity<U>>,
+) -> Column {
+    let unit = hours.iter().find_map(&field).map(|q| q.unit().symbol());
+    let values = hours.iter().map(|p| field(p).map(|q| q.value().to_string())).collect();
+    Column::new(header, unit, values)
+}
+
+fn plain(
+    hours: &[WeatherDataPoint],
+    header: &str,
+    unit: Option<&str>,
+    field: impl Fn(&WeatherDataPoint) -> Option<String>,
+) -> Column {
+    Column::new(header, unit, hours.iter().map(field).collect())
+}
+
+fn columns(hours: &[WeatherDataPoint]) -> Vec<Column> {
+    let number = |v: Option<f64>| v.map(|v| v.to_string());
+
+    vec![
+        plain(hours, "time", None, |p| {
+            Some(p.time.local().format("%Y-%m-%d %H:%M").to_string())
+        }),
+        quantity(hours, "airTemperature", |p| p.air_temperature),
+        quantity(hours, "wind.windSpeed", |p| p.wind.wind_speed),
+        quantity(hours, "wind.windDirection", |p| p.wind.wind_direction),
+        quantity(hours, "wind.gust", |p| p.wind.gust),
+        plain(hours, "wind.shoreWind", None, |p| {
+            p.wind.shore_wind.map(|s| s.name().to_string())
+        }),
+        quantity(hours, "waves.swellHeight", |p| p.waves.swell_height),
+        quantity(hours, "waves.swellPeriod", |p| p.waves.swell_period),
+        quantity(hours, "waves.swellDirection", |p| p.waves.swell_direction),
+        quantity(hours, "waves.windWaveHeight", |p| p.waves.wind_wave_height),
+        quantity(hours, "waves.windWavePeriod", |p| p.waves.wind_wave_period),
+        quantity(hours, "waves.windWaveDirection", |p| p.waves.wind_wave_direction),
+        quantity(hours, "waterTemperature", |p| p.water_temperature),
+        plain(hours, "clouds.cloudsCover", Some("%"), |p| number(p.clouds.cloud_cover)),
+        plain(hours, "clouds.lowcloudsCover", Some("%"), |p| {
+            number(p.clouds.low_cloud_cover)
+        }),
+        plain(hours, "clouds.mdiumCloudsCover", Some("%"), |p| {
+            number(p.clouds.medium_cloud_cover)
+        }),
+        plain(hours, "clouds.highCloudsCover", Some("%"), |p| {
+            number(p.clouds.high_cloud_cover)
+        }),
+        plain(hours, "precipitation", Some("mm/h"), |p| number(p.precipitation)),
+        quantity(hours, "tideHeight", |p| p.tide_height),
+        plain(hours, "isDaylight", None, |p| p.is_daylight.map(|d| d.to_string())),
+        plain(hours, "sailability.score", None, |p| {
+            p.sailability.as_ref().map(|s| s.score.to_string())
+        }),
+        plain(hours, "sailability.issues", None, |p| {
+            p.sailability
+                .as_ref()
+                .filter(|s| !s.issues.is_empty())
+                .map(|s| s.issues.join(" "))
+        }),
+        plain(hours, "gear.sailSize", Some("m²"), |p| {
+            p.gear.as_ref().map(|g| g.sail_size.to_string())
+        }),
+        plain(hours, "gear.fit", None, |p| {
+            p.gear.as_ref().map(|g| g.fit.name().to_string())
+        }),
+        plain(hours, "gear.board", None, |p| p.gear.as_ref().and_then(|g| g.board.clone())),
+    ]
+}
+
+fn escape(field: &str, separator: char) -> String {
+    if separator == '\t' {
+        return field.replace(['\t', '\n', '\r'], " ");
+    }
+    if field.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render `hours` as rows separated by `separator` (`,` or `\t`)
+pub fn render(hours: &[WeatherDataPoint], separator: char) -> String {
+    let columns: Vec<Column> = columns(hours).into_iter().filter(|c| !c.is_empty()).collect();
+    let sep = separator.to_string();
+
+    let mut lines = Vec::with_capacity(hours.len() + 1);
+    lines.push(
+        columns
+            .iter()
+            .map(|c| escape(&c.header, separator))
+            .collect::<Vec<_>>()
+            .join(&sep),
+    );
+    for row in 0..hours.len() {
+        lines.push(
+            columns
+                .iter()
+                .map(|c| c.values[row].as_deref().map_or(String::new(), |v| escape(v, separator)))
+                .collect::<Vec<_>>()
+                .join(&sep),
+        );
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::{convert_timezone, UtcTimestamp};
+    use crate::gear::{GearRecommendation, SailFit};
+    use crate::units::{degrees, Length, LengthUnit, Speed, SpeedUnit};
+    use chrono::DateTime;
+
+    fn hours() -> Vec<WeatherDataPoint> {
+        (0..2)
+            .map(|i| {
+                let utc = UtcTimestamp(DateTime::from_timestamp(1_765_108_800 + i * 3600, 0).unwrap());
+                let tz = "Asia/Jerusalem".parse().unwrap();
+                let mut point = WeatherDataPoint::empty(convert_timezone(utc, tz).unwrap());
+                point.wind.wind_speed = Some(Speed::new(15.5, SpeedUnit::Knots));
+                point.wind.wind_direction = Some(degrees(270.0));
+                point.is_daylight = Some(true);
+                point
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_flattens_sections_with_unit_headers() {
+        let mut hours = hours();
+        hours[1].waves.swell_height = Some(Length::new(1.2, LengthUnit::Meters));
+
+        let csv = render(&hours, ',');
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "time,wind.windSpeed [knots],wind.windDirection [°],waves.swellHeight [m],isDaylight"
+        );
+        assert_eq!(lines[1], "2025-12-07 14:00,15.5,270,,true");
+        assert_eq!(lines[2], "2025-12-07 15:00,15.5,270,1.2,true");
+    }
+
+    #[test]
+    fn test_fields_are_escaped_per_format() {
+        let mut hours = hours();
+        hours[0].gear = Some(GearRecommendation {
+            sail_size: 5.3,
+            fit: SailFit::Ok,
+            board: Some("Freeride \"130\", 2019".to_string()),
+        });
+
+        let csv = render(&hours, ',');
+        assert!(csv.contains(",5.3,ok,\"Freeride \"\"130\"\", 2019\"\n"), "{}", csv);
+
+        hours[0].gear.as_mut().unwrap().board = Some("Wave\t85".to_string());
+        let tsv = render(&hours, '\t');
+        assert!(tsv.lines().next().unwrap().ends_with("gear.sailSize [m²]\tgear.fit\tgear.board"));
+        assert!(tsv.contains("\t5.3\tok\tWave 85\n"), "{}", tsv);
+    }
+}
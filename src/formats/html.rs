@@ -0,0 +1,528 @@
+//! Self-contained HTML report with inline SVG charts
+//!
+//! The page needs no scripts, stylesheets or fonts from elsewhere, so it can
+//! be mailed or shared as a single file. It holds three charts over the same
+//! time axis (wind speed and gust with direction arrows, swell height and
+//! period, air and water temperature), followed by the session windows,
+//! tides and alerts. Every other local day is shaded and session windows
+//! are highlighted in all charts.
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use std::fmt::Write;
+
+use crate::forecast_provider::{TideKind, WeatherDataPoint};
+use crate::report::TransformedWeatherResponse;
+use crate::units::{Quantity, Unit};
+
+const WIDTH: f64 = 960.0;
+const HEIGHT: f64 = 220.0;
+const MARGIN_LEFT: f64 = 48.0;
+const MARGIN_RIGHT: f64 = 48.0;
+const MARGIN_TOP: f64 = 28.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em auto;max-width:1000px;color:#222}\
+h1{font-size:1.5em}h2{font-size:1.15em;margin-top:1.5em}\
+table{border-collapse:collapse}td,th{padding:.2em .8em;text-align:left;border-bottom:1px solid #ddd}\
+.meta{color:#555}svg{display:block;margin:.5em 0}\
+svg text{font-size:11px;fill:#555}.legend span{margin-right:1.5em}";
+
+/// Escape text for use in HTML content and attribute values
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Round `max` up to a value with an easy step for about five grid lines
+fn nice_max(max: f64) -> (f64, f64) {
+    if max <= 0.0 {
+        return (1.0, 0.2);
+    }
+    let rough = max / 5.0;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude);
+    ((max / step).ceil() * step, step)
+}
+
+/// Time axis shared by all charts
+struct Axis {
+    start: i64,
+    end: i64,
+}
+
+impl Axis {
+    fn x(&self, time: DateTime<Utc>) -> f64 {
+        let span = (self.end - self.start).max(1) as f64;
+        MARGIN_LEFT + (time.timestamp() - self.start) as f64 / span * (WIDTH - MARGIN_LEFT - MARGIN_RIGHT)
+    }
+}
+
+/// Vertical scale over the values, rounded out to whole steps
+struct Scale {
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl Scale {
+    /// With `from_zero` the scale also reaches 0, which suits amounts like
+    /// wind speed but flattens e.g. temperatures in kelvin
+    fn new(values: impl Iterator<Item = f64>, from_zero: bool) -> Self {
+        let start = if from_zero { (0.0, 0.0) } else { (f64::INFINITY, f64::NEG_INFINITY) };
+        let (min, max) = values.fold(start, |(lo, hi), v| (lo.min(v), hi.max(v)));
+        // No values at all
+        let (min, max) = if min > max { (0.0, 0.0) } else { (min, max) };
+        let (_, step) = nice_max(max - min);
+        let min = (min / step).floor() * step;
+        let max = ((max / step).ceil() * step).max(min + step);
+        Self { min, max, step }
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let plot = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        HEIGHT - MARGIN_BOTTOM - (value - self.min) / (self.max - self.min) * plot
+    }
+
+    fn ticks(&self) -> impl Iterator<Item = f64> + '_ {
+        let count = ((self.max - self.min) / self.step).round() as usize;
+        (0..=count).map(move |i| self.min + i as f64 * self.step)
+    }
+
+    /// Tick label with as many decimals as the step has, so 0.2 steps read
+    /// "0.6" rather than "0.6000000000000001"
+    fn label(&self, tick: f64) -> String {
+        let decimals = (-self.step.log10()).ceil().max(0.0) as usize;
+        // Adding 0.0 turns a -0.0 tick into 0.0
+        format!("{:.*}", decimals, tick + 0.0)
+    }
+}
+
+/// One series of a chart
+struct Series<'a> {
+    label: &'a str,
+    color: &'a str,
+    values: Vec<(DateTime<Utc>, Option<f64>)>,
+}
+
+fn series<U: Unit>(
+    hours: &[WeatherDataPoint],
+    field: impl Fn(&WeatherDataPoint) -> Option<Quantity<U>>,
+) -> Vec<(DateTime<Utc>, Option<f64>)> {
+    hours.iter().map(|p| (p.time.utc(), field(p).map(|q| q.value()))).collect()
+}
+
+fn unit<U: Unit>(hours: &[WeatherDataPoint], field: impl Fn(&WeatherDataPoint) -> Option<Quantity<U>>) -> &'static str {
+    hours.iter().find_map(field).map_or("", |q| q.unit().symbol())
+}
+
+/// Polyline segments of a series, broken where values are missing
+fn polylines(svg: &mut String, axis: &Axis, scale: &Scale, series: &Series) {
+    for run in series.values.split(|(_, v)| v.is_none()).filter(|run| !run.is_empty()) {
+        let points: Vec<String> = run
+            .iter()
+            .filter_map(|(t, v)| v.map(|v| format!("{:.1},{:.1}", axis.x(*t), scale.y(v))))
+            .collect();
+        let _ = write!(
+            svg,
+            r#"<polyline fill="none" stroke="{}" stroke-width="2" points="{}"/>"#,
+            series.color,
+            points.join(" ")
+        );
+    }
+}
+
+/// Local days of the forecast as `(date, first hour, end of last hour)`
+fn day_bands(hours: &[WeatherDataPoint]) -> Vec<(NaiveDate, DateTime<Utc>, DateTime<Utc>)> {
+    let mut bands: Vec<(NaiveDate, DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    for point in hours {
+        let date = point.time.local().date_naive();
+        let end = point.time.utc() + chrono::Duration::hours(1);
+        match bands.last_mut() {
+            Some(band) if band.0 == date => band.2 = end,
+            _ => bands.push((date, point.time.utc(), end)),
+        }
+    }
+    bands
+}
+
+/// Day shading, session highlights and hour ticks drawn behind a chart
+fn background(svg: &mut String, axis: &Axis, response: &TransformedWeatherResponse) {
+    let bottom = HEIGHT - MARGIN_BOTTOM;
+    for (i, (date, start, end)) in day_bands(&response.hours).iter().enumerate() {
+        let (x1, x2) = (axis.x(*start), axis.x(*end));
+        if i % 2 == 1 {
+            let _ = write!(
+                svg,
+                r##"<rect class="day" x="{:.1}" y="{}" width="{:.1}" height="{}" fill="#f1f4f8"/>"##,
+                x1,
+                MARGIN_TOP,
+                x2 - x1,
+                bottom - MARGIN_TOP
+            );
+        }
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            (x1 + x2) / 2.0,
+            HEIGHT - 8.0,
+            date.format("%a %d %b")
+        );
+    }
+
+    for session in &response.sessions {
+        let (x1, x2) = (axis.x(session.start.utc()), axis.x(session.end.utc()));
+        let _ = write!(
+            svg,
+            r##"<rect class="session" x="{:.1}" y="{}" width="{:.1}" height="{}" fill="#2e9e5b" fill-opacity="0.15"><title>Session, score {}</title></rect>"##,
+            x1,
+            MARGIN_TOP,
+            x2 - x1,
+            bottom - MARGIN_TOP,
+            session.score
+        );
+    }
+
+    for point in response.hours.iter().filter(|p| p.time.local().hour() % 6 == 0) {
+        let x = axis.x(point.time.utc());
+        let _ = write!(
+            svg,
+            r##"<line x1="{x:.1}" x2="{x:.1}" y1="{}" y2="{}" stroke="#ccc"/>"##,
+            bottom,
+            bottom + 4.0
+        );
+    }
+}
+
+/// A line chart with the left axis for `left` and, optionally, a right
+/// axis for `right`; the left axis starts at 0 when `from_zero` is set
+#[allow(clippy::too_many_arguments)]
+fn chart(
+    response: &TransformedWeatherResponse,
+    axis: &Axis,
+    title: &str,
+    left: &[Series],
+    left_unit: &str,
+    from_zero: bool,
+    right: Option<(&Series, &str)>,
+    overlay: impl Fn(&mut String, &Scale),
+) -> String {
+    let left_scale = Scale::new(left.iter().flat_map(|s| s.values.iter().filter_map(|(_, v)| *v)), from_zero);
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" role="img" aria-label="{}">"#,
+        WIDTH,
+        HEIGHT,
+        escape(title)
+    );
+    background(&mut svg, axis, response);
+
+    for tick in left_scale.ticks() {
+        let y = left_scale.y(tick);
+        let _ = write!(
+            svg,
+            r##"<line x1="{}" x2="{}" y1="{y:.1}" y2="{y:.1}" stroke="#e3e3e3"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+            MARGIN_LEFT,
+            WIDTH - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            left_scale.label(tick)
+        );
+    }
+    let _ = write!(svg, r#"<text x="{}" y="{}">{}</text>"#, 4.0, MARGIN_TOP - 10.0, escape(left_unit));
+
+    for series in left {
+        polylines(&mut svg, axis, &left_scale, series);
+    }
+
+    if let Some((series, unit)) = right {
+        let scale = Scale::new(series.values.iter().filter_map(|(_, v)| *v), true);
+        for tick in scale.ticks() {
+            let _ = write!(
+                svg,
+                r#"<text x="{}" y="{:.1}">{}</text>"#,
+                WIDTH - MARGIN_RIGHT + 6.0,
+                scale.y(tick) + 4.0,
+                scale.label(tick)
+            );
+        }
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            WIDTH - MARGIN_RIGHT + 6.0,
+            MARGIN_TOP - 10.0,
+            escape(unit)
+        );
+        polylines(&mut svg, axis, &scale, series);
+    }
+
+    overlay(&mut svg, &left_scale);
+    svg.push_str("</svg>");
+
+    let mut legend = String::from(r#"<div class="legend">"#);
+    for series in left.iter().chain(right.map(|(s, _)| s)) {
+        let _ = write!(
+            legend,
+            r#"<span><svg width="14" height="4" style="display:inline"><rect width="14" height="4" fill="{}"/></svg> {}</span>"#,
+            series.color,
+            escape(series.label)
+        );
+    }
+    legend.push_str("</div>");
+
+    format!("<h2>{}</h2>\n{}\n{}\n", escape(title), svg, legend)
+}
+
+/// Arrows along the top of the wind chart, pointing downwind
+fn direction_arrows(svg: &mut String, axis: &Axis, hours: &[WeatherDataPoint]) {
+    // Keep arrows at least ~16px apart
+    let every = ((hours.len() as f64 * 16.0) / (WIDTH - MARGIN_LEFT - MARGIN_RIGHT)).ceil().max(1.0) as usize;
+    for point in hours.iter().step_by(every) {
+        if let Some(direction) = point.wind.wind_direction {
+            let _ = write!(
+                svg,
+                r##"<path d="M0,-6 L4,4 L0,2 L-4,4 Z" fill="#333" transform="translate({:.1},{}) rotate({:.0})"><title>{:.0}°</title></path>"##,
+                axis.x(point.time.utc()),
+                MARGIN_TOP - 14.0,
+                direction.value() + 180.0,
+                direction.value()
+            );
+        }
+    }
+}
+
+/// Render the whole report as one HTML page
+pub fn render(response: &TransformedWeatherResponse) -> String {
+    let hours = &response.hours;
+    let meta = &response.meta;
+    let axis = Axis {
+        start: hours.first().map_or(0, |p| p.time.utc().timestamp()),
+        end: hours.last().map_or(0, |p| p.time.utc().timestamp() + 3600),
+    };
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Windsurf forecast {}</title>\n<style>{}</style>\n</head>\n<body>\n\
+         <h1>Windsurf forecast</h1>\n\
+         <p class=\"meta\">{} hours, {} &ndash; {}<br>Location {:.4}, {:.4} &middot; provider {} &middot; generated {}</p>\n",
+        escape(&meta.start),
+        STYLE,
+        hours.len(),
+        escape(&meta.start),
+        escape(&meta.end),
+        meta.lat,
+        meta.lng,
+        escape(&meta.provider),
+        escape(&meta.report_generated_at)
+    );
+
+    let wind_unit = unit(hours, |p| p.wind.wind_speed);
+    html.push_str(&chart(
+        response,
+        &axis,
+        "Wind",
+        &[
+            Series { label: "Wind speed", color: "#1f6fb2", values: series(hours, |p| p.wind.wind_speed) },
+            Series { label: "Gust", color: "#e07b39", values: series(hours, |p| p.wind.gust) },
+        ],
+        wind_unit,
+        true,
+        None,
+        |svg, _| direction_arrows(svg, &axis, hours),
+    ));
+
+    let period = Series { label: "Swell period", color: "#8e5bb5", values: series(hours, |p| p.waves.swell_period) };
+    html.push_str(&chart(
+        response,
+        &axis,
+        "Swell",
+        &[Series { label: "Swell height", color: "#178a94", values: series(hours, |p| p.waves.swell_height) }],
+        unit(hours, |p| p.waves.swell_height),
+        true,
+        Some((&period, unit(hours, |p| p.waves.swell_period))),
+        |_, _| {},
+    ));
+
+    html.push_str(&chart(
+        response,
+        &axis,
+        "Temperature",
+        &[
+            Series { label: "Air", color: "#c0392b", values: series(hours, |p| p.air_temperature) },
+            Series { label: "Water", color: "#2874a6", values: series(hours, |p| p.water_temperature) },
+        ],
+        unit(hours, |p| p.air_temperature.or(p.water_temperature)),
+        false,
+        None,
+        |_, _| {},
+    ));
+
+    html.push_str("<h2>Session windows</h2>\n");
+    if response.sessions.is_empty() {
+        html.push_str("<p>No session windows match the rider thresholds.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Day</th><th>Time</th><th>Hours</th><th>Score</th><th>Peak</th><th>Gear</th></tr>\n");
+        for session in &response.sessions {
+            let gear = session.gear.as_ref().map_or(String::new(), |g| {
+                let board = g.board.as_deref().map_or(String::new(), |b| format!(", {}", escape(b)));
+                format!("{:.1} m²{}", g.sail_size, board)
+            });
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{} &ndash; {}</td><td>{}</td><td>{}</td><td>{} at {}</td><td>{}</td></tr>",
+                session.start.local().format("%a %d %b"),
+                session.start.local().format("%H:%M"),
+                session.end.local().format("%H:%M"),
+                session.hours,
+                session.score,
+                escape(&session.peak.wind_speed.to_string()),
+                session.peak.time.local().format("%H:%M"),
+                gear
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !response.tides.is_empty() {
+        html.push_str("<h2>Tides</h2>\n<table>\n<tr><th>Time</th><th>Tide</th><th>Height</th></tr>\n");
+        for tide in &response.tides {
+            let kind = match tide.kind {
+                TideKind::High => "High",
+                TideKind::Low => "Low",
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{:.2} {}</td></tr>",
+                tide.time.local().format("%a %d %b %H:%M"),
+                kind,
+                tide.height.value(),
+                tide.height.unit().symbol()
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !response.alerts.is_empty() {
+        html.push_str("<h2>Alerts</h2>\n");
+        for alert in &response.alerts {
+            let _ = writeln!(
+                html,
+                "<p><strong>{}</strong> ({}, {} &ndash; {})<br>{}</p>",
+                escape(&alert.event),
+                escape(&alert.sender),
+                alert.start.local().format("%a %d %b %H:%M"),
+                alert.end.local().format("%a %d %b %H:%M"),
+                escape(&alert.description)
+            );
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::LocalTimestamp;
+    use crate::sessions::{SessionPeak, SessionWindow};
+    use crate::test_utils::{point_at, report};
+    use crate::units::{degrees, Speed, SpeedUnit};
+
+    fn time(ts: i64) -> LocalTimestamp {
+        point_at(ts, chrono_tz::Asia::Jerusalem).time
+    }
+
+    /// 48 hours from 2025-12-07 00:00 Jerusalem time
+    fn response() -> TransformedWeatherResponse {
+        let hours = (0..48)
+            .map(|i| {
+                let mut point = point_at(1_765_058_400 + i * 3600, chrono_tz::Asia::Jerusalem);
+                point.wind.wind_speed = Some(Speed::new(10.0 + (i % 12) as f64, SpeedUnit::Knots));
+                point.wind.wind_direction = Some(degrees(270.0));
+                point
+            })
+            .collect();
+
+        let mut response = report(hours);
+        response.sessions = vec![SessionWindow {
+            start: time(1_765_058_400 + 10 * 3600),
+            end: time(1_765_058_400 + 13 * 3600),
+            hours: 3,
+            score: 81,
+            peak: SessionPeak {
+                time: time(1_765_058_400 + 11 * 3600),
+                wind_speed: Speed::new(21.0, SpeedUnit::Knots),
+                gust: None,
+            },
+            gear: None,
+        }];
+        response.meta.lat = 32.8329;
+        response.meta.lng = 34.9547;
+        response.meta.start = "2025-12-07T00:00:00+02:00".to_string();
+        response.meta.end = "2025-12-08T23:59:59+02:00".to_string();
+        response.meta.report_generated_at = "2025-12-06 20:00".to_string();
+        response.meta.provider = "<windy>".to_string();
+        response
+    }
+
+    #[test]
+    fn test_report_is_self_contained_with_charts() {
+        let html = render(&response());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<svg xmlns").count(), 3);
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\"http"), "no external resources");
+        assert!(html.contains("provider &lt;windy&gt;"), "provider name is escaped");
+        assert!(html.contains("48 hours"));
+    }
+
+    #[test]
+    fn test_days_are_shaded_and_sessions_highlighted() {
+        let html = render(&response());
+
+        // Second day of two is shaded in each of the three charts
+        assert_eq!(html.matches(r#"class="day""#).count(), 3);
+        assert_eq!(html.matches(r#"class="session""#).count(), 3);
+        assert!(html.contains("Sun 07 Dec") && html.contains("Mon 08 Dec"));
+        assert!(html.contains("<td>10:00 &ndash; 13:00</td>"));
+        assert!(html.contains("rotate(450)"), "westerly arrows point east");
+    }
+
+    #[test]
+    fn test_scales_round_to_easy_steps() {
+        assert_eq!(nice_max(23.0), (25.0, 5.0));
+        assert_eq!(nice_max(1.3), (1.5, 0.5));
+        assert_eq!(nice_max(0.9), (1.0, 0.2));
+        assert_eq!(nice_max(0.0), (1.0, 0.2));
+
+        let scale = Scale::new([-3.0, 12.0].into_iter(), true);
+        assert_eq!((scale.min, scale.max, scale.step), (-5.0, 15.0, 5.0));
+        assert_eq!(scale.ticks().collect::<Vec<_>>(), vec![-5.0, 0.0, 5.0, 10.0, 15.0]);
+        let labels = |scale: &Scale| scale.ticks().map(|t| scale.label(t)).collect::<Vec<_>>();
+        assert_eq!(labels(&scale), ["-5", "0", "5", "10", "15"]);
+
+        // Typical swell heights: 0.2 and 0.1 m steps
+        let swell = Scale::new([0.4, 0.9].into_iter(), true);
+        assert_eq!(swell.step, 0.2);
+        assert_eq!(labels(&swell), ["0.0", "0.2", "0.4", "0.6", "0.8", "1.0"]);
+        let calm = Scale::new([0.05, 0.45].into_iter(), true);
+        assert_eq!(calm.step, 0.1);
+        assert_eq!(labels(&calm), ["0.0", "0.1", "0.2", "0.3", "0.4", "0.5"]);
+
+        // Temperatures in kelvin keep to the range of the data
+        let kelvin = Scale::new([288.4, 296.1].into_iter(), false);
+        assert_eq!(labels(&kelvin), ["288", "290", "292", "294", "296", "298"]);
+        let empty = Scale::new(std::iter::empty(), false);
+        assert_eq!((empty.min, empty.max), (0.0, 0.2));
+    }
+}
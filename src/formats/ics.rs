@@ -0,0 +1,329 @@
+//! iCalendar export of the session windows
+//!
+//! Every session window becomes a `VEVENT` summarised as wind range,
+//! direction and shore classification ("18–24 kn W, cross-shore"). Events
+//! are written in the configured timezone, with a `VTIMEZONE` covering the
+//! offsets in effect during the forecast window.
+//!
+//! UIDs are built from the spot, the local date and the position of the
+//! session within that day, not from its exact times, so a calendar
+//! subscribed to the file updates a session that moved by an hour instead
+//! of showing it twice.
+
+use chrono::{DateTime, Duration, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use std::collections::HashMap;
+
+use crate::config::ResolvedConfig;
+use crate::forecast_provider::WeatherDataPoint;
+use crate::forecast_window::ForecastWindow;
+use crate::report::TransformedWeatherResponse;
+use crate::sessions::SessionWindow;
+use crate::shore::ShoreWind;
+use crate::units::{SpeedUnit, Unit};
+
+use super::table::compass_point;
+
+/// Escape a TEXT value (RFC 5545, section 3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line to at most 75 octets per line
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+fn local_time(time: NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// `VTIMEZONE` with one observance per offset in effect during `window`
+///
+/// Transitions are located to the minute by bisecting the hour in which
+/// the offset changes.
+fn vtimezone(tz: Tz, window: &ForecastWindow) -> Vec<String> {
+    let offset_at = |time: DateTime<Utc>| tz.offset_from_utc_datetime(&time.naive_utc());
+
+    let start = offset_at(window.start);
+    let mut observances = vec![(window.start, start, start)];
+    let mut time = window.start;
+    while time < window.end {
+        let next = time + Duration::hours(1);
+        let previous = observances.last().map(|o| o.2).unwrap_or(start);
+        if offset_at(next).fix() != previous.fix() {
+            let (mut before, mut after) = (time, next);
+            while after - before > Duration::minutes(1) {
+                let middle = before + (after - before) / 2;
+                if offset_at(middle).fix() == previous.fix() {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            observances.push((after, previous, offset_at(after)));
+        }
+        time = next;
+    }
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    for (onset, from, to) in observances {
+        let kind = if to.dst_offset().is_zero() { "STANDARD" } else { "DAYLIGHT" };
+        let from_seconds = from.fix().local_minus_utc();
+        lines.extend([
+            format!("BEGIN:{}", kind),
+            format!("DTSTART:{}", local_time(onset.naive_utc() + Duration::seconds(from_seconds as i64))),
+            format!("TZOFFSETFROM:{}", utc_offset(from_seconds)),
+            format!("TZOFFSETTO:{}", utc_offset(to.fix().local_minus_utc())),
+            format!("TZNAME:{}", to.abbreviation()),
+            format!("END:{}", kind),
+        ]);
+    }
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+/// Event title, e.g. "18–24 kn W, cross-shore"
+///
+/// The range is the lowest to highest wind speed over the session's hours;
+/// direction and shore classification are those of the peak hour.
+fn summary(session: &SessionWindow, hours: &[&WeatherDataPoint]) -> String {
+    let speeds: Vec<f64> = hours.iter().filter_map(|p| p.wind.wind_speed).map(|s| s.value()).collect();
+    let low = speeds.iter().copied().fold(f64::INFINITY, f64::min).round();
+    let high = speeds.iter().copied().fold(f64::NEG_INFINITY, f64::max).round();
+    let unit = match session.peak.wind_speed.unit() {
+        SpeedUnit::Knots => "kn",
+        unit => unit.symbol(),
+    };
+
+    let mut summary = if speeds.is_empty() || low == high {
+        format!("{:.0} {}", session.peak.wind_speed.value(), unit)
+    } else {
+        format!("{:.0}–{:.0} {}", low, high, unit)
+    };
+
+    let peak = hours.iter().find(|p| p.time == session.peak.time);
+    if let Some(direction) = peak.and_then(|p| p.wind.wind_direction) {
+        summary.push(' ');
+        summary.push_str(compass_point(direction.value()));
+    }
+    if let Some(shore) = peak.and_then(|p| p.wind.shore_wind) {
+        summary.push_str(", ");
+        summary.push_str(match shore {
+            ShoreWind::Cross => "cross-shore",
+            shore => shore.name(),
+        });
+    }
+    summary
+}
+
+fn description(session: &SessionWindow, provider: &str) -> String {
+    let mut lines = vec![format!(
+        "Score {} over {} h, peak {} at {}",
+        session.score,
+        session.hours,
+        session.peak.wind_speed,
+        session.peak.time.local().format("%H:%M")
+    )];
+    if let Some(gust) = session.peak.gust {
+        lines[0].push_str(&format!(" (gusts {})", gust));
+    }
+    if let Some(gear) = &session.gear {
+        let board = gear.board.as_deref().map_or(String::new(), |b| format!(", {}", b));
+        lines.push(format!("Gear: {:.1} m² sail{}", gear.sail_size, board));
+    }
+    lines.push(format!("Forecast: {}", provider));
+    lines.join("\n")
+}
+
+/// Render the session windows of `response` as an iCalendar file
+pub fn render(
+    response: &TransformedWeatherResponse,
+    config: &ResolvedConfig,
+    window: &ForecastWindow,
+    now: DateTime<Utc>,
+) -> String {
+    let tz = config.timezone;
+    let spot = config.spot.as_ref().map(|s| s.name.as_str());
+    let place = spot.map_or(format!("{:.4}, {:.4}", config.lat, config.lng), str::to_string);
+    let uid_key = spot.map_or(format!("{:.4}_{:.4}", config.lat, config.lng), |name| {
+        name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect()
+    });
+    let stamp = now.format("%Y%m%dT%H%M%SZ");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//windsurf-forecast//Session windows//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:Windsurf sessions – {}", escape(&place)),
+        format!("X-WR-TIMEZONE:{}", tz.name()),
+    ];
+    lines.extend(vtimezone(tz, window));
+
+    let mut per_day: HashMap<chrono::NaiveDate, usize> = HashMap::new();
+    for session in &response.sessions {
+        let start = session.start.local();
+        let ordinal = per_day.entry(start.date_naive()).or_insert(0);
+        *ordinal += 1;
+
+        let hours: Vec<&WeatherDataPoint> = response
+            .hours
+            .iter()
+            .filter(|p| p.time.utc() >= session.start.utc() && p.time.utc() < session.end.utc())
+            .collect();
+
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{}-{}@windsurf-forecast", start.format("%Y%m%d"), ordinal, uid_key),
+            format!("DTSTAMP:{}", stamp),
+            format!("LAST-MODIFIED:{}", stamp),
+            format!("DTSTART;TZID={}:{}", tz.name(), local_time(start.naive_local())),
+            format!("DTEND;TZID={}:{}", tz.name(), local_time(session.end.local().naive_local())),
+            format!("SUMMARY:{}", escape(&summary(session, &hours))),
+            format!("LOCATION:{}", escape(&place)),
+            format!("GEO:{:.6};{:.6}", config.lat, config.lng),
+            format!("DESCRIPTION:{}", escape(&description(session, &response.meta.provider))),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = lines.iter().map(|l| fold(l)).collect::<Vec<_>>().join("\r\n");
+    output.push_str("\r\n");
+    output
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::ResolvedSpot;
+    use crate::forecast_provider::LocalTimestamp;
+    use crate::sessions::SessionPeak;
+    use crate::test_utils::{create_resolved_config, point_at, report};
+    use crate::units::{degrees, Speed};
+
+    const JERUSALEM: Tz = chrono_tz::Asia::Jerusalem;
+
+    fn time(ts: i64) -> LocalTimestamp {
+        point_at(ts, JERUSALEM).time
+    }
+
+    fn config() -> ResolvedConfig {
+        ResolvedConfig {
+            provider: "windy".to_string(),
+            spot: Some(ResolvedSpot {
+                name: "bat galim".to_string(),
+                config: toml::from_str("lat = 32.8329\nlng = 34.9547").unwrap(),
+                tide_table: None,
+            }),
+            timezone: JERUSALEM,
+            lat: 32.8329,
+            lng: 34.9547,
+            ..create_resolved_config()
+        }
+    }
+
+    /// A session from 12:00 to 15:00 Jerusalem time on 2025-12-07
+    fn response() -> TransformedWeatherResponse {
+        let start = 1_765_101_600;
+        let hours = [18.0, 24.0, 21.0]
+            .iter()
+            .enumerate()
+            .map(|(i, wind)| {
+                let mut point = point_at(start + i as i64 * 3600, JERUSALEM);
+                point.wind.wind_speed = Some(Speed::new(*wind, SpeedUnit::Knots));
+                point.wind.wind_direction = Some(degrees(268.0));
+                point.wind.shore_wind = Some(ShoreWind::Cross);
+                point
+            })
+            .collect();
+
+        let mut response = report(hours);
+        response.sessions = vec![SessionWindow {
+            start: time(start),
+            end: time(start + 3 * 3600),
+            hours: 3,
+            score: 78,
+            peak: SessionPeak {
+                time: time(start + 3600),
+                wind_speed: Speed::new(24.0, SpeedUnit::Knots),
+                gust: None,
+            },
+            gear: None,
+        }];
+        response.meta.lat = 32.8329;
+        response.meta.lng = 34.9547;
+        response.meta.provider = "Windy.com (GFS, ECMWF)".to_string();
+        response
+    }
+
+    fn window(first_day: &str, days: u32) -> ForecastWindow {
+        let now = format!("{}T06:00:00Z", first_day).parse().unwrap();
+        ForecastWindow::local_days(now, 0, days, JERUSALEM)
+    }
+
+    #[test]
+    fn test_sessions_become_events_with_stable_uids() {
+        let ics = render(&response(), &config(), &window("2025-12-07", 2), DateTime::UNIX_EPOCH);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nUID:20251207-1-bat_galim@windsurf-forecast\r\n"));
+        assert!(ics.contains("\r\nDTSTART;TZID=Asia/Jerusalem:20251207T120000\r\n"));
+        assert!(ics.contains("\r\nDTEND;TZID=Asia/Jerusalem:20251207T150000\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:18–24 kn W\\, cross-shore\r\n"), "{}", ics);
+        assert!(ics.contains("\r\nLOCATION:bat galim\r\n"));
+        assert!(ics.replace("\r\n ", "").contains("\\nForecast: Windy.com (GFS\\, ECMWF)\r\n"));
+        assert!(ics.contains("\r\nTZOFFSETTO:+0200\r\n"));
+
+        // Re-running later with the session moved by an hour keeps its UID
+        let mut moved = response();
+        moved.sessions[0].start = time(1_765_105_200);
+        let rerun = render(&moved, &config(), &window("2025-12-07", 2), Utc::now());
+        assert!(rerun.contains("\r\nUID:20251207-1-bat_galim@windsurf-forecast\r\n"));
+    }
+
+    #[test]
+    fn test_timezone_lists_dst_transition_in_window() {
+        // Israel leaves daylight saving time at 02:00 on 2025-10-26
+        let ics = render(&response(), &config(), &window("2025-10-25", 3), DateTime::UNIX_EPOCH);
+        let timezone = &ics[ics.find("BEGIN:VTIMEZONE").unwrap()..ics.find("END:VTIMEZONE").unwrap()];
+
+        assert!(timezone.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20251026T020000\r\nTZOFFSETFROM:+0300\r\nTZOFFSETTO:+0200\r\n"
+        ));
+        assert_eq!(timezone.matches("BEGIN:DAYLIGHT").count(), 1);
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(60));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -0,0 +1,90 @@
+//! Output formats selected with `--format`
+//!
+//! Every format renders a [`TransformedWeatherResponse`]. JSON is written
+//! as is; the other formats live in their own submodules. All formats are
+//! written to a file except `table`, which is printed to stdout.
+//!
+//! Formats that need more than the report (the calendar needs the spot,
+//! timezone and forecast window) take them from the resolved configuration.
+
+use anyhow::Result;
+use chrono::Utc;
+use clap::ValueEnum;
+
+use crate::config::ResolvedConfig;
+use crate::forecast_window::ForecastWindow;
+use crate::report::TransformedWeatherResponse;
+
+pub mod delimited;
+pub mod html;
+pub mod ics;
+pub mod table;
+
+/// File format of the forecast report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// Comma-separated hours, with the metadata in a `.meta.json` sidecar
+    Csv,
+    /// Tab-separated hours, with the metadata in a `.meta.json` sidecar
+    Tsv,
+    /// Table printed to the terminal
+    Table,
+    /// Single HTML page with inline SVG charts
+    Html,
+    /// iCalendar file with one event per session window
+    Ics,
+}
+
+impl OutputFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Table => "table",
+            OutputFormat::Html => "html",
+            OutputFormat::Ics => "ics",
+        }
+    }
+
+    /// File name extension, without the dot
+    pub fn extension(self) -> &'static str {
+        self.name()
+    }
+
+    /// Whether the report is printed to stdout instead of written to a file
+    pub fn prints_to_stdout(self) -> bool {
+        matches!(self, OutputFormat::Table)
+    }
+
+    /// Whether every run writes the same file rather than one per start
+    /// date, so calendar apps can subscribe to it
+    pub fn has_fixed_file_name(self) -> bool {
+        matches!(self, OutputFormat::Ics)
+    }
+
+    /// Whether the metadata is written to a separate `.meta.json` file
+    pub fn has_meta_sidecar(self) -> bool {
+        matches!(self, OutputFormat::Csv | OutputFormat::Tsv)
+    }
+
+    /// Render the report in this format
+    pub fn render(
+        self,
+        response: &TransformedWeatherResponse,
+        config: &ResolvedConfig,
+        window: &ForecastWindow,
+    ) -> Result<String> {
+        match self {
+            OutputFormat::Json => Ok(serde_json::to_string_pretty(response)?),
+            OutputFormat::Csv => Ok(delimited::render(&response.hours, ',')),
+            OutputFormat::Tsv => Ok(delimited::render(&response.hours, '\t')),
+            OutputFormat::Table => Ok(table::render(response, table::use_color())),
+            OutputFormat::Html => Ok(html::render(response)),
+            OutputFormat::Ics => Ok(ics::render(response, config, window, Utc::now())),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Terminal table of the hourly data
+//!
+//! One line per hour, grouped under a separator for every local day. Wind
+//! and swell directions are shown as an arrow pointing where the wind or
+//! swell travels, followed by the 16-point compass direction it comes from.
+//!
+//! On a terminal, wind speed and gust are colored by Beaufort force and
+//! night hours are dimmed. Colors are left out when stdout is not a terminal
+//! or `NO_COLOR` is set, so piped output stays plain text.
+
+use std::io::IsTerminal;
+
+use crate::forecast_provider::WeatherDataPoint;
+use crate::report::TransformedWeatherResponse;
+use crate::units::{Quantity, SpeedUnit, Unit};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+
+/// Upper bound in knots and ANSI color of each wind band (Beaufort forces
+/// 0-2, 3, 4, 5, 6, 7 and 8+)
+const WIND_BANDS: [(f64, &str); 7] = [
+    (7.0, "\x1b[34m"),
+    (11.0, "\x1b[36m"),
+    (17.0, "\x1b[32m"),
+    (22.0, "\x1b[33m"),
+    (28.0, "\x1b[31m"),
+    (34.0, "\x1b[1;31m"),
+    (f64::INFINITY, "\x1b[1;35m"),
+];
+
+const COMPASS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Arrows pointing north, north-east, ... north-west
+const ARROWS: [&str; 8] = ["↑", "↗", "→", "↘", "↓", "↙", "←", "↖"];
+
+/// Whether table output should use ANSI colors
+pub fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Compass point a direction in degrees comes from, e.g. "WSW"
+pub fn compass_point(degrees: f64) -> &'static str {
+    COMPASS[((degrees.rem_euclid(360.0) / 22.5).round() as usize) % 16]
+}
+
+/// Arrow pointing where something coming from `degrees` travels to
+pub fn arrow(degrees: f64) -> &'static str {
+    ARROWS[(((degrees + 180.0).rem_euclid(360.0) / 45.0).round() as usize) % 8]
+}
+
+fn direction(degrees: Option<f64>) -> String {
+    degrees.map_or("-".to_string(), |d| format!("{} {}", arrow(d), compass_point(d)))
+}
+
+fn value<U: Unit>(q: Option<Quantity<U>>, precision: usize) -> String {
+    q.map_or("-".to_string(), |q| format!("{:.*}", precision, q.value()))
+}
+
+/// Symbol of the first value of a field, for the unit header line
+fn unit<U: Unit>(hours: &[WeatherDataPoint], field: impl Fn(&WeatherDataPoint) -> Option<Quantity<U>>) -> &'static str {
+    hours.iter().find_map(field).map_or("", |q| q.unit().symbol())
+}
+
+fn wind_color(speed: Option<Quantity<SpeedUnit>>) -> &'static str {
+    let Some(knots) = speed.map(|s| s.to(SpeedUnit::Knots).value()) else {
+        return "";
+    };
+    WIND_BANDS
+        .iter()
+        .find(|(limit, _)| knots < *limit)
+        .map_or("", |(_, color)| color)
+}
+
+/// Pad `text` to `width` and wrap it in `color` when colors are on
+fn cell(text: &str, width: usize, color: &str, use_color: bool) -> String {
+    if use_color && !color.is_empty() {
+        format!("{}{:>width$}{}", color, text, RESET, width = width)
+    } else {
+        format!("{:>width$}", text, width = width)
+    }
+}
+
+/// Render the hours of `response` as a table
+pub fn render(response: &TransformedWeatherResponse, use_color: bool) -> String {
+    let hours = &response.hours;
+    let show_tide = hours.iter().any(|p| p.tide_height.is_some());
+    let mut lines = Vec::new();
+
+    let mut header = format!(
+        "{:>5}  {:>5} {:>5} {:>5}  {:>5} {:>4}  {:>5} {:>4}  {:>5}",
+        "Time", "Wind", "Gust", "Dir", "Swell", "Per", "Dir", "Air", "Score"
+    );
+    let mut units = format!(
+        "{:>5}  {:>5} {:>5} {:>5}  {:>5} {:>4}  {:>5} {:>4}  {:>5}",
+        "",
+        unit(hours, |p| p.wind.wind_speed),
+        unit(hours, |p| p.wind.gust),
+        "from",
+        unit(hours, |p| p.waves.swell_height),
+        unit(hours, |p| p.waves.swell_period),
+        "from",
+        unit(hours, |p| p.air_temperature),
+        ""
+    );
+    if show_tide {
+        header.push_str(&format!("  {:>5}", "Tide"));
+        units.push_str(&format!("  {:>5}", unit(hours, |p| p.tide_height)));
+    }
+    lines.push(header);
+    lines.push(units);
+
+    let mut current_day = None;
+    for point in hours {
+        let local = point.time.local();
+        if current_day != Some(local.date_naive()) {
+            current_day = Some(local.date_naive());
+            lines.push(format!("── {} {}", local.format("%a %d %b %Y"), "─".repeat(40)));
+        }
+
+        let score = point
+            .sailability
+            .as_ref()
+            .filter(|s| s.is_sailable())
+            .map_or(String::new(), |s| s.score.to_string());
+        let mut line = format!(
+            "{:>5}  {} {} {:>5}  {:>5} {:>4}  {:>5} {:>4}  {:>5}",
+            local.format("%H:%M"),
+            cell(&value(point.wind.wind_speed, 1), 5, wind_color(point.wind.wind_speed), use_color),
+            cell(&value(point.wind.gust, 1), 5, wind_color(point.wind.gust), use_color),
+            direction(point.wind.wind_direction.map(|d| d.value())),
+            value(point.waves.swell_height, 1),
+            value(point.waves.swell_period, 0),
+            direction(point.waves.swell_direction.map(|d| d.value())),
+            value(point.air_temperature, 0),
+            score
+        );
+        if show_tide {
+            line.push_str(&format!("  {:>5}", value(point.tide_height, 2)));
+        }
+
+        if use_color && point.is_daylight == Some(false) {
+            // Re-apply dimming after each colored cell resets it
+            line = format!("{}{}{}", DIM, line.replace(RESET, &format!("{}{}", RESET, DIM)), RESET);
+        }
+        lines.push(line);
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{point_at, report};
+    use crate::units::{degrees, Speed};
+
+    fn point(ts: i64, wind: f64, direction: f64) -> WeatherDataPoint {
+        let mut point = point_at(ts, chrono_tz::Asia::Jerusalem);
+        point.wind.wind_speed = Some(Speed::new(wind, SpeedUnit::Knots));
+        point.wind.wind_direction = Some(degrees(direction));
+        point
+    }
+
+    #[test]
+    fn test_directions_point_downwind() {
+        assert_eq!((arrow(270.0), compass_point(270.0)), ("→", "W"));
+        assert_eq!((arrow(0.0), compass_point(0.0)), ("↓", "N"));
+        assert_eq!((arrow(225.0), compass_point(225.0)), ("↗", "SW"));
+        assert_eq!(compass_point(355.0), "N");
+        assert_eq!(compass_point(250.0), "WSW");
+    }
+
+    #[test]
+    fn test_plain_table_has_day_separators_and_no_escape_codes() {
+        // 2025-12-07 23:00 and 2025-12-08 00:00 in Jerusalem
+        let table = render(
+            &report(vec![point(1_765_141_200, 18.0, 270.0), point(1_765_144_800, 8.0, 45.0)]),
+            false,
+        );
+
+        assert!(!table.contains('\x1b'));
+        let separators: Vec<&str> = table.lines().filter(|l| l.starts_with("──")).collect();
+        assert_eq!(separators.len(), 2);
+        assert!(separators[0].contains("Sun 07 Dec 2025"));
+        assert!(separators[1].contains("Mon 08 Dec 2025"));
+        assert!(table.contains("23:00   18.0     -   → W"), "{}", table);
+        assert!(table.contains("00:00    8.0     -  ↙ NE"), "{}", table);
+    }
+
+    #[test]
+    fn test_wind_is_colored_by_band() {
+        let table = render(&report(vec![point(1_765_141_200, 18.0, 270.0)]), true);
+        assert!(table.contains("\x1b[33m 18.0\x1b[0m"), "{:?}", table);
+        assert_eq!(wind_color(Some(Speed::new(40.0, SpeedUnit::Knots))), "\x1b[1;35m");
+        assert_eq!(wind_color(Some(Speed::new(3.0, SpeedUnit::MetersPerSecond))), "\x1b[34m");
+    }
+}
@@ -0,0 +1,212 @@
+//! Sail size and board recommendations
+//!
+//! The ideal sail area follows the common rule of thumb
+//! `area (m²) = weight (kg) × 1.34 / wind (knots)`, scaled down for less
+//! experienced riders. Instead of the mean wind speed, the wind used is
+//! pulled towards the gust by a skill-dependent share of the gust margin,
+//! so beginners are sized for the gusts rather than the lulls.
+//!
+//! From the quiver, the largest sail not above the ideal area is chosen.
+//! When every sail is bigger the smallest one is reported as overpowered;
+//! when even the largest sail is well below the ideal it is underpowered.
+//!
+//! The board is the one whose volume is closest to the rider's weight plus
+//! a skill-dependent float margin, reduced as the wind picks up.
+
+use serde::Serialize;
+
+use crate::config::loader::{RiderConfig, SkillLevel};
+use crate::units::{Speed, SpeedUnit};
+
+/// Rule-of-thumb constant relating weight and wind to sail area
+const SAIL_CONSTANT: f64 = 1.34;
+
+/// Largest sail counts as underpowered below this share of the ideal area
+const UNDERPOWERED_RATIO: f64 = 0.85;
+
+/// Board volume margin shrinks by this many liters per knot above 15 knots
+const VOLUME_PER_KNOT: f64 = 2.0;
+
+/// How the recommended sail compares to the ideal area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SailFit {
+    Underpowered,
+    Ok,
+    Overpowered,
+}
+
+impl SailFit {
+    pub fn name(self) -> &'static str {
+        match self {
+            SailFit::Underpowered => "underpowered",
+            SailFit::Ok => "ok",
+            SailFit::Overpowered => "overpowered",
+        }
+    }
+}
+
+/// Recommended gear for an hour or a session window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GearRecommendation {
+    /// Sail area in m²
+    #[serde(rename = "sailSize")]
+    pub sail_size: f64,
+
+    pub fit: SailFit,
+
+    /// Name of the recommended board, when a quiver of boards is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub board: Option<String>,
+}
+
+struct SkillProfile {
+    /// Share of the gust margin added to the wind speed
+    gust_weight: f64,
+    /// Multiplier on the rule-of-thumb sail area
+    sail_factor: f64,
+    /// Liters of board volume above body weight at 15 knots
+    float_margin: f64,
+}
+
+fn profile(skill: SkillLevel) -> SkillProfile {
+    match skill {
+        SkillLevel::Beginner => SkillProfile {
+            gust_weight: 0.7,
+            sail_factor: 0.85,
+            float_margin: 80.0,
+        },
+        SkillLevel::Intermediate => SkillProfile {
+            gust_weight: 0.5,
+            sail_factor: 0.95,
+            float_margin: 40.0,
+        },
+        SkillLevel::Advanced => SkillProfile {
+            gust_weight: 0.3,
+            sail_factor: 1.0,
+            float_margin: 10.0,
+        },
+    }
+}
+
+/// Recommend gear for `wind` and `gust`
+///
+/// Returns `None` when the rider's weight is not configured or there is no
+/// wind to size a sail for.
+pub fn recommend(wind: Speed, gust: Option<Speed>, rider: &RiderConfig) -> Option<GearRecommendation> {
+    let weight = rider.weight?;
+    let profile = profile(rider.skill);
+
+    let wind = wind.to(SpeedUnit::Knots).value();
+    let gust = gust.map_or(wind, |g| g.to(SpeedUnit::Knots).value().max(wind));
+    let effective = wind + profile.gust_weight * (gust - wind);
+    if effective <= 0.0 {
+        return None;
+    }
+
+    let ideal = weight * SAIL_CONSTANT / effective * profile.sail_factor;
+    let (sail_size, fit) = pick_sail(ideal, &rider.sails);
+
+    let target_volume =
+        (weight + profile.float_margin - VOLUME_PER_KNOT * (effective - 15.0).max(0.0)).max(weight);
+    let board = rider
+        .boards
+        .iter()
+        .min_by(|a, b| {
+            (a.volume - target_volume)
+                .abs()
+                .total_cmp(&(b.volume - target_volume).abs())
+        })
+        .map(|board| board.name.clone());
+
+    Some(GearRecommendation {
+        sail_size,
+        fit,
+        board,
+    })
+}
+
+fn pick_sail(ideal: f64, sails: &[f64]) -> (f64, SailFit) {
+    let largest_fitting = sails.iter().copied().filter(|&s| s <= ideal).reduce(f64::max);
+    let smallest = sails.iter().copied().reduce(f64::min);
+
+    match (largest_fitting, smallest) {
+        (Some(sail), _) if sail < ideal * UNDERPOWERED_RATIO => (sail, SailFit::Underpowered),
+        (Some(sail), _) => (sail, SailFit::Ok),
+        (None, Some(sail)) => (sail, SailFit::Overpowered),
+        (None, None) => ((ideal * 10.0).round() / 10.0, SailFit::Ok),
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader::BoardConfig;
+
+    fn rider(skill: SkillLevel, sails: &[f64]) -> RiderConfig {
+        RiderConfig {
+            weight: Some(75.0),
+            skill,
+            sails: sails.to_vec(),
+            boards: vec![
+                BoardConfig { name: "Beginner 190".to_string(), volume: 190.0 },
+                BoardConfig { name: "Freeride 130".to_string(), volume: 130.0 },
+                BoardConfig { name: "Wave 85".to_string(), volume: 85.0 },
+            ],
+            ..RiderConfig::default()
+        }
+    }
+
+    fn knots(value: f64) -> Speed {
+        Speed::new(value, SpeedUnit::Knots)
+    }
+
+    #[test]
+    fn test_unconstrained_sail_follows_rule_of_thumb() {
+        let gear = recommend(knots(20.0), None, &rider(SkillLevel::Advanced, &[])).unwrap();
+        // 75 × 1.34 / 20 = 5.025
+        assert_eq!(gear.sail_size, 5.0);
+        assert_eq!(gear.fit, SailFit::Ok);
+        assert_eq!(gear.board.as_deref(), Some("Wave 85"));
+    }
+
+    #[test]
+    fn test_beginners_rig_smaller_for_gusty_wind() {
+        let quiver = [4.0, 4.5, 5.3, 6.2, 7.0];
+        let gust = Some(knots(22.0));
+
+        let advanced = recommend(knots(15.0), gust, &rider(SkillLevel::Advanced, &quiver)).unwrap();
+        let beginner = recommend(knots(15.0), gust, &rider(SkillLevel::Beginner, &quiver)).unwrap();
+
+        assert_eq!((advanced.sail_size, advanced.fit), (5.3, SailFit::Ok));
+        assert_eq!((beginner.sail_size, beginner.fit), (4.0, SailFit::Ok));
+        assert_eq!(advanced.board.as_deref(), Some("Wave 85"));
+        assert_eq!(beginner.board.as_deref(), Some("Freeride 130"));
+    }
+
+    #[test]
+    fn test_quiver_limits_report_fit() {
+        let quiver = [5.3, 6.2];
+        let rider = rider(SkillLevel::Advanced, &quiver);
+
+        let strong = recommend(knots(30.0), None, &rider).unwrap();
+        assert_eq!((strong.sail_size, strong.fit), (5.3, SailFit::Overpowered));
+
+        let light = recommend(knots(10.0), None, &rider).unwrap();
+        assert_eq!((light.sail_size, light.fit), (6.2, SailFit::Underpowered));
+
+        let metric = recommend(Speed::new(9.0, SpeedUnit::MetersPerSecond), None, &rider).unwrap();
+        assert_eq!((metric.sail_size, metric.fit), (5.3, SailFit::Ok));
+    }
+
+    #[test]
+    fn test_no_recommendation_without_weight_or_wind() {
+        let mut rider = rider(SkillLevel::Intermediate, &[]);
+        assert!(recommend(knots(0.0), None, &rider).is_none());
+
+        rider.weight = None;
+        assert!(recommend(knots(20.0), None, &rider).is_none());
+    }
+}
@@ -0,0 +1,909 @@
+//! Shared HTTP client with an on-disk response cache
+//!
+//! Providers send their requests through [`HttpClient`], which reuses one
+//! `reqwest::Client` for the whole run and keeps successful response bodies
+//! under the cache directory. A cached body is reused while it is younger
+//! than the configured TTL, so re-running the same query (e.g. to try
+//! another output format) does not spend API quota.
+//!
+//! Entries are keyed by provider, model, coordinates rounded to two
+//! decimals (about 1 km), endpoint and forecast window, never by the raw
+//! request, so API keys do not end up in file names. The endpoint is a hash
+//! of the request URL without its query, so a run against an `[endpoints]`
+//! override neither reads nor fills the entries of the default API.
+//!
+//! In offline mode nothing is sent: each request is answered with the most
+//! recently fetched entry for the same provider, model, location and
+//! endpoint whose window reaches into the requested one, however old it is.
+//!
+//! With `--record <dir>` every exchange is also written to `dir` as a
+//! fixture (request with secrets redacted, status and body), one file per
+//! provider, model and location. `--replay <dir>` answers requests from
+//! those files instead of the network, so a recorded run can be re-parsed
+//! for a bug report or a golden-file test.
+//!
+//! Requests that reach the network have a timeout and are retried on
+//! transient failures (connection errors, timeouts, HTTP 429 and 5xx) with
+//! jittered exponential backoff, or after the delay a `Retry-After` header
+//! asks for. See [`RetryPolicy`].
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use thiserror::Error;
+
+/// How the response cache is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve fresh entries, store new responses
+    ReadWrite,
+    /// Always fetch, store the new responses (`--refresh`)
+    Refresh,
+    /// Neither read nor write the cache (`--no-cache`)
+    Disabled,
+    /// Only serve cached entries, whatever their age (`--offline`)
+    Offline,
+}
+
+/// Resolved cache location, lifetime and mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheSettings {
+    pub dir: PathBuf,
+    pub ttl: Duration,
+    pub mode: CacheMode,
+}
+
+impl CacheSettings {
+    pub fn disabled() -> Self {
+        Self {
+            dir: PathBuf::new(),
+            ttl: Duration::ZERO,
+            mode: CacheMode::Disabled,
+        }
+    }
+}
+
+/// Whether exchanges are recorded to or replayed from a fixture directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum FixtureMode {
+    #[default]
+    Off,
+    /// Write every exchange to the directory (`--record`)
+    Record(PathBuf),
+    /// Answer requests from the directory, never the network (`--replay`)
+    Replay(PathBuf),
+}
+
+/// Attempts, per-attempt timeout and backoff for requests sent to a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    pub timeout: Duration,
+    /// Delay before the first retry; doubles with every further retry
+    pub base_delay: Duration,
+    /// Upper bound of a backoff delay. A `Retry-After` asking for longer is
+    /// not waited for: the response is returned as it is.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            timeout: Duration::from_secs(30),
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1-based): exponential, capped at
+    /// `max_delay`, and jittered between half and all of it so that
+    /// concurrent runs do not retry in lockstep
+    fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << retry.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        delay / 2 + delay.mul_f64(random_fraction() / 2.0)
+    }
+}
+
+/// Uniform-ish number in [0, 1]; each `RandomState` is seeded differently
+fn random_fraction() -> f64 {
+    RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64
+}
+
+/// HTTP statuses worth another attempt
+fn is_transient(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Delay requested by a `Retry-After` header, in seconds or as an HTTP date
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// What a request asks for, independent of how the provider encodes it
+pub struct CacheKey<'a> {
+    pub provider: &'a str,
+    /// Model or endpoint within the provider, e.g. "gfsWave" or "sea-level"
+    pub model: &'a str,
+    pub lat: f64,
+    pub lng: f64,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+const WINDOW_FORMAT: &str = "%Y%m%dT%H%M";
+
+impl CacheKey<'_> {
+    /// Provider, model and rounded coordinates, safe for a file name
+    fn location_name(&self) -> String {
+        let name = format!("{}_{}_{:.2}_{:.2}", self.provider, self.model, self.lat, self.lng);
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '_' })
+            .collect()
+    }
+
+    /// File name up to the window, shared by all windows of a location on
+    /// one endpoint
+    fn prefix(&self, endpoint: &str) -> String {
+        format!("{}_{}_", self.location_name(), endpoint)
+    }
+
+    fn file_name(&self, endpoint: &str) -> String {
+        format!(
+            "{}{}_{}.json",
+            self.prefix(endpoint),
+            self.start.format(WINDOW_FORMAT),
+            self.end.format(WINDOW_FORMAT)
+        )
+    }
+
+    /// Fixture file name: the location without endpoint or window, so a
+    /// replay finds the recording whatever date it runs on
+    fn fixture_name(&self) -> String {
+        format!("{}.json", self.location_name())
+    }
+
+    /// End of the window encoded in a cache file name
+    fn window_end(file_name: &str) -> Option<DateTime<Utc>> {
+        let end = file_name.strip_suffix(".json")?.rsplit('_').next()?;
+        Some(NaiveDateTime::parse_from_str(end, WINDOW_FORMAT).ok()?.and_utc())
+    }
+
+    fn location(&self) -> String {
+        format!("{} {} response for ({:.2}, {:.2})", self.provider, self.model, self.lat, self.lng)
+    }
+}
+
+/// Short stable hash of `url` without its query, naming the endpoint in
+/// cache file names
+///
+/// FNV-1a rather than `std::hash`, whose output may change between Rust
+/// releases and would orphan the cache.
+fn endpoint_id(url: &reqwest::Url) -> String {
+    let mut url = url.clone();
+    url.set_query(None);
+    url.set_fragment(None);
+    let hash = url
+        .as_str()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("{:08x}", hash as u32 ^ (hash >> 32) as u32)
+}
+
+/// Why a request could not be answered
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+
+    /// Offline and no usable cache entry; the message says what was found
+    #[error("{0}")]
+    Offline(String),
+
+    /// A fixture could not be recorded or replayed
+    #[error("{0}")]
+    Fixture(String),
+}
+
+impl HttpError {
+    /// Error for a provider to return: request failures get `context`,
+    /// cache and fixture misses are explained as they are
+    pub fn with_context(self, context: &'static str) -> anyhow::Error {
+        match self {
+            HttpError::Request(e) => anyhow::Error::new(e).context(context),
+            other => other.into(),
+        }
+    }
+}
+
+/// Status and body of a response, whether fetched or cached
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: DateTime<Utc>,
+    status: u16,
+    body: String,
+}
+
+/// A recorded exchange, as written by `--record` and read by `--replay`
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    recorded_at: DateTime<Utc>,
+    window: [DateTime<Utc>; 2],
+    request: RecordedRequest,
+    status: u16,
+    /// Parsed JSON when the body is JSON, so fixtures stay readable
+    body: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<serde_json::Value>,
+}
+
+/// Query parameters and JSON body fields that carry API keys
+const SECRET_PARAMS: &[&str] = &["appid", "key", "apikey", "api_key", "token"];
+const REDACTED: &str = "REDACTED";
+
+impl RecordedRequest {
+    /// Method, URL and JSON body of `request` with API keys replaced
+    fn redacted(request: &reqwest::Request) -> Self {
+        let mut url = request.url().clone();
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if SECRET_PARAMS.contains(&name.as_ref()) { REDACTED.into() } else { value.into_owned() };
+                (name.into_owned(), value)
+            })
+            .collect();
+        if !pairs.is_empty() {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(bytes).ok())
+            .map(|mut body| {
+                if let Some(fields) = body.as_object_mut() {
+                    for (name, value) in fields.iter_mut() {
+                        if SECRET_PARAMS.contains(&name.as_str()) {
+                            *value = REDACTED.into();
+                        }
+                    }
+                }
+                body
+            });
+
+        Self {
+            method: request.method().to_string(),
+            url: url.to_string(),
+            body,
+        }
+    }
+}
+
+/// When the fixtures in `dir` were recorded (the earliest exchange), so a
+/// replayed run computes the same forecast window as the recorded one
+pub fn replay_time(dir: &Path) -> anyhow::Result<DateTime<Utc>> {
+    fs::read_dir(dir)
+        .map_err(|e| anyhow::anyhow!("Cannot read replay directory {}: {}", dir.display(), e))?
+        .flatten()
+        .filter(|file| file.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|file| serde_json::from_str::<Fixture>(&fs::read_to_string(file.path()).ok()?).ok())
+        .map(|fixture| fixture.recorded_at)
+        .min()
+        .ok_or_else(|| anyhow::anyhow!("No recorded exchanges in {}", dir.display()))
+}
+
+/// Human-readable age, e.g. "42 min" or "3 h 5 min"
+pub fn format_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes().max(0);
+    match minutes {
+        0..=59 => format!("{} min", minutes),
+        60..=2879 => format!("{} h {} min", minutes / 60, minutes % 60),
+        _ => format!("{} days", minutes / 1440),
+    }
+}
+
+/// One `reqwest::Client` (and its connection pool) for the whole process
+fn shared_client() -> reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new).clone()
+}
+
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    cache: CacheSettings,
+    fixtures: FixtureMode,
+    retry: RetryPolicy,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(CacheSettings::disabled())
+    }
+}
+
+impl HttpClient {
+    pub fn new(cache: CacheSettings) -> Self {
+        Self {
+            client: shared_client(),
+            cache,
+            fixtures: FixtureMode::Off,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Client with the cache, fixture and retry settings of a resolved config
+    pub fn from_config(config: &crate::config::ResolvedConfig) -> Self {
+        Self::new(config.cache.clone())
+            .with_fixtures(config.fixtures.clone())
+            .with_retry(config.retry.clone())
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
+    pub fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.post(url)
+    }
+
+    fn cache_path(&self, key: &CacheKey, endpoint: &str) -> PathBuf {
+        self.cache.dir.join(key.file_name(endpoint))
+    }
+
+    fn load_entry(path: &Path) -> Option<CacheEntry> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    fn read_cache(&self, path: &Path) -> Option<CacheEntry> {
+        let entry = Self::load_entry(path)?;
+        let age = (Utc::now() - entry.fetched_at).to_std().unwrap_or(Duration::ZERO);
+        (age < self.cache.ttl).then_some(entry)
+    }
+
+    /// Newest entry for the key's location and endpoint whose window ends
+    /// after the requested start
+    fn read_offline(&self, key: &CacheKey, endpoint: &str) -> Result<CacheEntry, HttpError> {
+        let prefix = key.prefix(endpoint);
+        let mut entries: Vec<(CacheEntry, DateTime<Utc>)> = fs::read_dir(&self.cache.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|file| {
+                let name = file.file_name().into_string().ok()?;
+                let window_end = CacheKey::window_end(name.strip_prefix(&prefix)?)?;
+                Some((Self::load_entry(&file.path())?, window_end))
+            })
+            .collect();
+        entries.sort_by_key(|(entry, _)| std::cmp::Reverse(entry.fetched_at));
+
+        let newest = entries.first().map(|(entry, _)| entry.fetched_at);
+        match entries.into_iter().find(|(_, window_end)| *window_end > key.start) {
+            Some((entry, _)) => Ok(entry),
+            None => Err(HttpError::Offline(match newest {
+                Some(fetched_at) => format!(
+                    "Offline: the newest cached {} is {} old and ends before the requested forecast window",
+                    key.location(),
+                    format_age(Utc::now() - fetched_at)
+                ),
+                None => format!(
+                    "Offline: no cached {} in {}; run once with a connection to fill the cache",
+                    key.location(),
+                    self.cache.dir.display()
+                ),
+            })),
+        }
+    }
+
+    fn write_cache(&self, path: &Path, response: &HttpResponse) -> anyhow::Result<()> {
+        let entry = CacheEntry {
+            fetched_at: Utc::now(),
+            status: response.status.as_u16(),
+            body: response.body.clone(),
+        };
+        fs::create_dir_all(&self.cache.dir)?;
+        fs::write(path, serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+
+    fn replay(&self, dir: &Path, key: &CacheKey) -> Result<HttpResponse, HttpError> {
+        let path = dir.join(key.fixture_name());
+        let text = fs::read_to_string(&path).map_err(|_| {
+            HttpError::Fixture(format!("Replay: no recorded {} in {}", key.location(), dir.display()))
+        })?;
+        let fixture: Fixture = serde_json::from_str(&text)
+            .map_err(|e| HttpError::Fixture(format!("Replay: cannot parse {}: {}", path.display(), e)))?;
+        let status = reqwest::StatusCode::from_u16(fixture.status)
+            .map_err(|_| HttpError::Fixture(format!("Replay: invalid status in {}", path.display())))?;
+        let body = match fixture.body {
+            serde_json::Value::String(body) => body,
+            body => body.to_string(),
+        };
+        Ok(HttpResponse { status, body })
+    }
+
+    fn record(
+        dir: &Path,
+        key: &CacheKey,
+        request: RecordedRequest,
+        response: &HttpResponse,
+    ) -> anyhow::Result<()> {
+        let fixture = Fixture {
+            recorded_at: Utc::now(),
+            window: [key.start, key.end],
+            request,
+            status: response.status.as_u16(),
+            body: serde_json::from_str(&response.body)
+                .unwrap_or_else(|_| serde_json::Value::String(response.body.clone())),
+        };
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(key.fixture_name()), serde_json::to_string_pretty(&fixture)?)?;
+        Ok(())
+    }
+
+    /// One attempt: the response with the delay its `Retry-After` asks for
+    async fn attempt(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<(HttpResponse, Option<Duration>), reqwest::Error> {
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+        let wait = retry_after(response.headers());
+        let body = response.text().await?;
+        Ok((HttpResponse { status, body }, wait))
+    }
+
+    /// Send `request`, retrying transient failures as the policy allows
+    ///
+    /// Returns the last response (e.g. a 503 after all attempts) or error.
+    async fn execute(
+        &self,
+        key: &CacheKey<'_>,
+        mut request: reqwest::Request,
+    ) -> Result<HttpResponse, HttpError> {
+        let mut attempt = 1;
+        loop {
+            // Streaming bodies cannot be cloned, and so cannot be retried
+            let next = if attempt < self.retry.max_attempts { request.try_clone() } else { None };
+            let outcome = self.attempt(request).await;
+
+            let retry = match (&outcome, next) {
+                (Ok((response, retry_after)), Some(next)) if is_transient(response.status) => {
+                    let wait = retry_after.unwrap_or_else(|| self.retry.backoff(attempt));
+                    (wait <= self.retry.max_delay)
+                        .then(|| (format!("got HTTP {}", response.status.as_u16()), wait, next))
+                }
+                (Err(e), Some(next)) if e.is_connect() || e.is_timeout() => {
+                    let reason = if e.is_timeout() { "timed out" } else { "could not connect" };
+                    Some((reason.to_string(), self.retry.backoff(attempt), next))
+                }
+                _ => None,
+            };
+            let Some((reason, wait, next)) = retry else {
+                return outcome.map(|(response, _)| response).map_err(HttpError::from);
+            };
+
+            eprintln!(
+                "⚠️  {} {} request {}; retrying in {:.1} s (attempt {} of {})",
+                key.provider,
+                key.model,
+                reason,
+                wait.as_secs_f64(),
+                attempt + 1,
+                self.retry.max_attempts
+            );
+            tokio::time::sleep(wait).await;
+            request = next;
+            attempt += 1;
+        }
+    }
+
+    /// Send `request`, or answer it from the cache or a replayed fixture
+    ///
+    /// Only successful responses with a body are cached; errors always go
+    /// back to the provider, which knows how to explain them. A cache that
+    /// cannot be written only produces a warning. While recording, the cache
+    /// is not read so every exchange really reaches the provider, and any
+    /// response (errors included) is written out.
+    pub async fn send(
+        &self,
+        key: &CacheKey<'_>,
+        request: reqwest::RequestBuilder,
+    ) -> Result<HttpResponse, HttpError> {
+        if let FixtureMode::Replay(dir) = &self.fixtures {
+            return self.replay(dir, key);
+        }
+
+        let mut request = request.build()?;
+        let endpoint = endpoint_id(request.url());
+        let path = self.cache_path(key, &endpoint);
+
+        if self.cache.mode == CacheMode::Offline {
+            let entry = self.read_offline(key, &endpoint)?;
+            eprintln!(
+                "Offline: using the {} cached {} ago",
+                key.location(),
+                format_age(Utc::now() - entry.fetched_at)
+            );
+            let status = reqwest::StatusCode::from_u16(entry.status)
+                .map_err(|_| HttpError::Offline(format!("Offline: corrupt cache entry for {}", key.location())))?;
+            return Ok(HttpResponse { status, body: entry.body });
+        }
+
+        let recording = matches!(self.fixtures, FixtureMode::Record(_));
+        if self.cache.mode == CacheMode::ReadWrite && !recording {
+            if let Some(entry) = self.read_cache(&path) {
+                if let Ok(status) = reqwest::StatusCode::from_u16(entry.status) {
+                    eprintln!(
+                        "Using cached {} {} response ({} old)",
+                        key.provider,
+                        key.model,
+                        format_age(Utc::now() - entry.fetched_at)
+                    );
+                    return Ok(HttpResponse { status, body: entry.body });
+                }
+            }
+        }
+
+        *request.timeout_mut() = Some(self.retry.timeout);
+        let recorded = recording.then(|| RecordedRequest::redacted(&request));
+        let response = self.execute(key, request).await?;
+        let status = response.status;
+
+        if let (FixtureMode::Record(dir), Some(recorded)) = (&self.fixtures, recorded) {
+            Self::record(dir, key, recorded, &response).map_err(|e| {
+                HttpError::Fixture(format!("Cannot record {} in {}: {:#}", key.location(), dir.display(), e))
+            })?;
+        }
+
+        let cacheable = status.is_success() && status != reqwest::StatusCode::NO_CONTENT;
+        if self.cache.mode != CacheMode::Disabled && cacheable {
+            if let Err(e) = self.write_cache(&path, &response) {
+                eprintln!("⚠️  Could not cache response in {}: {:#}", self.cache.dir.display(), e);
+            }
+        }
+        Ok(response)
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn key(model: &str) -> CacheKey<'_> {
+        CacheKey {
+            provider: "test",
+            model,
+            lat: 32.486722,
+            lng: -34.888722,
+            start: "2025-12-07T00:00:00Z".parse().unwrap(),
+            end: "2025-12-11T00:00:00Z".parse().unwrap(),
+        }
+    }
+
+    fn endpoint(url: &str) -> String {
+        endpoint_id(&url.parse().unwrap())
+    }
+
+    fn client(dir: &Path, mode: CacheMode) -> HttpClient {
+        HttpClient::new(CacheSettings {
+            dir: dir.to_path_buf(),
+            ttl: Duration::from_secs(3600),
+            mode,
+        })
+        .with_retry(retry(1))
+    }
+
+    fn retry(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_key_rounds_coordinates() {
+        assert_eq!(key("gfsWave").file_name("0123abcd"), "test_gfsWave_32.49_-34.89_0123abcd_20251207T0000_20251211T0000.json");
+        assert_eq!(key("a/b").file_name("0123abcd"), "test_a_b_32.49_-34.89_0123abcd_20251207T0000_20251211T0000.json");
+    }
+
+    #[test]
+    fn test_endpoint_ignores_query_but_not_base_url() {
+        let api = endpoint("https://api.example.com/v2/point?key=secret");
+        assert_eq!(api.len(), 8);
+        assert_eq!(api, endpoint("https://api.example.com/v2/point?key=other"));
+        assert_ne!(api, endpoint("http://localhost:8080/v2/point?key=secret"));
+    }
+
+    #[tokio::test]
+    async fn test_base_urls_do_not_share_cache_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let http = client(dir.path(), CacheMode::ReadWrite);
+
+        // Same key against the default API and a local stand-in: each is
+        // fetched once and then served from its own entry
+        let production = MockServer::start_async().await;
+        let stand_in = MockServer::start_async().await;
+        for (server, body) in [(&production, "production"), (&stand_in, "stand-in")] {
+            let mock = server
+                .mock_async(|when, then| {
+                    when.method(GET).path("/v1/point");
+                    then.status(200).body(body);
+                })
+                .await;
+            for _ in 0..2 {
+                let response = http.send(&key("weather"), http.get(&server.url("/v1/point"))).await.unwrap();
+                assert_eq!(response.body, body);
+            }
+            assert_eq!(mock.hits_async().await, 1);
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_second_request_is_served_from_cache() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/point");
+                then.status(200).body(r#"{"hours":[]}"#);
+            })
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let http = client(dir.path(), CacheMode::ReadWrite);
+
+        for _ in 0..2 {
+            let response = http.send(&key("weather"), http.get(&server.url("/point"))).await.unwrap();
+            assert_eq!(response.body, r#"{"hours":[]}"#);
+        }
+        assert_eq!(mock.hits_async().await, 1);
+
+        // --refresh fetches again and --no-cache neither reads nor writes
+        let refresh = client(dir.path(), CacheMode::Refresh);
+        refresh.send(&key("weather"), refresh.get(&server.url("/point"))).await.unwrap();
+        let disabled = client(dir.path(), CacheMode::Disabled);
+        disabled.send(&key("other"), disabled.get(&server.url("/point"))).await.unwrap();
+        assert_eq!(mock.hits_async().await, 3);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_expired_entries_are_not_served() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/point");
+                then.status(503).body("busy");
+            })
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let http = client(dir.path(), CacheMode::ReadWrite);
+
+        for _ in 0..2 {
+            let response = http.send(&key("weather"), http.get(&server.url("/point"))).await.unwrap();
+            assert_eq!(response.status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        }
+        assert_eq!(mock.hits_async().await, 2);
+
+        let stale = CacheEntry {
+            fetched_at: Utc::now() - chrono::Duration::hours(2),
+            status: 200,
+            body: "old".to_string(),
+        };
+        let path = http.cache_path(&key("weather"), &endpoint(&server.url("/point")));
+        fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+        assert!(http.read_cache(&path).is_none());
+    }
+
+    /// Cache `body` for `key`, as fetched from the offline tests' endpoint
+    fn write_entry(http: &HttpClient, key: &CacheKey, age_hours: i64, body: &str) {
+        let entry = CacheEntry {
+            fetched_at: Utc::now() - chrono::Duration::hours(age_hours),
+            status: 200,
+            body: body.to_string(),
+        };
+        fs::write(http.cache_path(key, &endpoint("http://127.0.0.1:9/point")), serde_json::to_string(&entry).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_offline_serves_newest_overlapping_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let http = client(dir.path(), CacheMode::Offline);
+        let day = chrono::Duration::days(1);
+
+        // Fetched yesterday for the window starting yesterday, and two days
+        // ago for the window starting two days ago
+        let yesterday = CacheKey { start: key("weather").start - day, end: key("weather").end - day, ..key("weather") };
+        let older = CacheKey { start: yesterday.start - day, end: yesterday.end - day, ..key("weather") };
+        write_entry(&http, &yesterday, 30, "yesterday");
+        write_entry(&http, &older, 50, "older");
+
+        // Nothing listens on the discard port; a request would fail
+        let request = http.get("http://127.0.0.1:9/point");
+        let response = http.send(&key("weather"), request).await.unwrap();
+        assert_eq!(response.body, "yesterday");
+    }
+
+    #[tokio::test]
+    async fn test_offline_miss_explains_what_was_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let http = client(dir.path(), CacheMode::Offline);
+
+        let err = http.send(&key("weather"), http.get("http://127.0.0.1:9/point")).await.unwrap_err();
+        let message = err.with_context("Failed to connect").to_string();
+        assert!(message.starts_with("Offline: no cached test weather response for (32.49, -34.89)"), "{}", message);
+
+        let week = chrono::Duration::days(7);
+        let stale = CacheKey { start: key("weather").start - week, end: key("weather").end - week, ..key("weather") };
+        write_entry(&http, &stale, 170, "stale");
+        let err = http.send(&key("weather"), http.get("http://127.0.0.1:9/point")).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Offline: the newest cached test weather response for (32.49, -34.89) is 7 days old \
+             and ends before the requested forecast window"
+        );
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(chrono::Duration::minutes(42)), "42 min");
+        assert_eq!(format_age(chrono::Duration::minutes(185)), "3 h 5 min");
+        assert_eq!(format_age(chrono::Duration::days(3)), "3 days");
+    }
+
+    #[tokio::test]
+    async fn test_recorded_exchange_replays_without_network() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/point");
+                then.status(200).body(r#"{"ts":[1]}"#);
+            })
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = client(dir.path(), CacheMode::ReadWrite)
+            .with_fixtures(FixtureMode::Record(dir.path().join("fixtures")));
+
+        for _ in 0..2 {
+            let request = recorder
+                .post(&format!("{}?appid=secret&units=metric", server.url("/point")))
+                .json(&serde_json::json!({ "lat": 32.49, "key": "secret" }));
+            recorder.send(&key("gfs"), request).await.unwrap();
+        }
+        // Recording bypasses the cache so every exchange is captured
+        assert_eq!(mock.hits_async().await, 2);
+
+        let fixture = fs::read_to_string(dir.path().join("fixtures/test_gfs_32.49_-34.89.json")).unwrap();
+        assert!(!fixture.contains("secret"));
+        assert!(fixture.contains("appid=REDACTED&units=metric"));
+
+        let replayer = HttpClient::default().with_fixtures(FixtureMode::Replay(dir.path().join("fixtures")));
+        let response = replayer.send(&key("gfs"), replayer.get("http://127.0.0.1:9/point")).await.unwrap();
+        assert_eq!(response.status, reqwest::StatusCode::OK);
+        assert_eq!(response.body, r#"{"ts":[1]}"#);
+        assert!(replay_time(&dir.path().join("fixtures")).unwrap() <= Utc::now());
+
+        let err = replayer.send(&key("gfsWave"), replayer.get("http://127.0.0.1:9/point")).await.unwrap_err();
+        assert!(err.to_string().starts_with("Replay: no recorded test gfsWave response"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let server = MockServer::start_async().await;
+        let busy = server
+            .mock_async(|when, then| {
+                when.path("/busy");
+                then.status(503).body("busy");
+            })
+            .await;
+        let missing = server
+            .mock_async(|when, then| {
+                when.path("/missing");
+                then.status(404);
+            })
+            .await;
+        let http = HttpClient::default().with_retry(retry(3));
+
+        let response = http.send(&key("weather"), http.get(&server.url("/busy"))).await.unwrap();
+        assert_eq!(response.status, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(busy.hits_async().await, 3);
+
+        http.send(&key("weather"), http.get(&server.url("/missing"))).await.unwrap();
+        assert_eq!(missing.hits_async().await, 1);
+
+        let err = http.send(&key("weather"), http.get("http://127.0.0.1:9/point")).await.unwrap_err();
+        assert!(matches!(err, HttpError::Request(e) if e.is_connect()));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_honored_up_to_max_delay() {
+        let server = MockServer::start_async().await;
+        let soon = server
+            .mock_async(|when, then| {
+                when.path("/soon");
+                then.status(429).header("Retry-After", "0");
+            })
+            .await;
+        let later = server
+            .mock_async(|when, then| {
+                when.path("/later");
+                then.status(429).header("Retry-After", "120");
+            })
+            .await;
+        let http = HttpClient::default().with_retry(retry(2));
+
+        http.send(&key("weather"), http.get(&server.url("/soon"))).await.unwrap();
+        assert_eq!(soon.hits_async().await, 2);
+
+        // Waiting two minutes is worse than reporting the 429 now
+        let response = http.send(&key("weather"), http.get(&server.url("/later"))).await.unwrap();
+        assert_eq!(response.status, reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(later.hits_async().await, 1);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            ..RetryPolicy::default()
+        };
+        for (retry, low, high) in [(1, 50, 100), (3, 200, 400), (10, 500, 1000)] {
+            let delay = policy.backoff(retry).as_millis();
+            assert!((low..=high).contains(&delay), "retry {}: {} ms", retry, delay);
+        }
+    }
+
+    #[test]
+    fn test_retry_after_accepts_seconds_and_dates() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(reqwest::header::RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+}
@@ -2,10 +2,22 @@
 // Exposes modules for testing
 
 pub mod args;
+pub mod batch;
 pub mod config;
+pub mod ensemble;
 pub mod forecast_provider;
+pub mod forecast_window;
+pub mod formats;
+pub mod gear;
+pub mod http;
 pub mod provider_registry;
 pub mod providers;
+pub mod report;
+pub mod sessions;
+pub mod shore;
+pub mod sun;
+pub mod tide;
+pub mod units;
 
 // Test utilities - available for both unit tests and integration tests
 // This module is only compiled during testing (not in production builds)
@@ -7,93 +7,192 @@ use std::collections::HashMap;
 use std::fs;
 
 mod args;
+mod batch;
 mod config;
+mod ensemble;
 mod forecast_provider;
+mod forecast_window;
+mod formats;
+mod gear;
+mod http;
 mod provider_registry;
 mod providers;
+mod report;
+mod sessions;
+mod shore;
+mod sun;
+mod tide;
+mod units;
+
+// The library's test helpers, for the unit tests compiled into the binary
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_utils;
 
 use args::{validate_args, Args};
-use config::{check_timezone_match, pick_timezone_interactive};
-use forecast_provider::WeatherDataPoint;
+use batch::SpotRanking;
+use config::{check_timezone_match, pick_timezone_interactive, ResolvedConfig};
+use ensemble::EnsembleHour;
+use forecast_provider::{WeatherData, WeatherDataPoint};
+use forecast_window::ForecastWindow;
+use formats::OutputFormat;
+use report::{TransformedMetaData, TransformedWeatherResponse};
+use sessions::SessionWindow;
+use units::{Quantity, Unit};
 
 // ============================================================================
 // Data Structures for Output
 // ============================================================================
 
 #[derive(Debug, Serialize)]
-struct TransformedMetaData {
+struct EnsembleMetaData {
     lat: f64,
     lng: f64,
     start: String,
     end: String,
     #[serde(rename = "report_generated_at")]
     report_generated_at: String,
-    provider: String,
+    providers: Vec<String>,
+    thresholds: HashMap<String, f64>,
     units: HashMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
-struct TransformedWeatherResponse {
-    hours: Vec<WeatherDataPoint>,
-    meta: TransformedMetaData,
+struct EnsembleWeatherResponse {
+    hours: Vec<EnsembleHour>,
+    meta: EnsembleMetaData,
+}
+
+#[derive(Debug, Serialize)]
+struct SpotIndexMetaData {
+    #[serde(rename = "report_generated_at")]
+    report_generated_at: String,
+    days_ahead: i32,
+    first_day_offset: i32,
+    criterion: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SpotIndexResponse {
+    spots: Vec<SpotRanking>,
+    meta: SpotIndexMetaData,
 }
 
 // ============================================================================
 // Transformation Functions
 // ============================================================================
 
-fn create_units_map(provider_name: &str) -> HashMap<String, String> {
-    let wind_unit = match provider_name {
-        "stormglass" => "knots",
-        "openweathermap" => "m/s",
-        _ => "m/s",
-    };
+/// Symbol of the unit the first reported value of a field is expressed in
+fn unit_symbol<U: Unit>(
+    hours: &[WeatherDataPoint],
+    field: impl Fn(&WeatherDataPoint) -> Option<Quantity<U>>,
+) -> Option<&'static str> {
+    hours.iter().find_map(field).map(|q| q.unit().symbol())
+}
 
-    [
+/// Describe the units of the output fields, derived from the data itself
+///
+/// Typed fields without any value in the forecast are left out.
+fn create_units_map(hours: &[WeatherDataPoint]) -> HashMap<String, String> {
+    let typed = [
         (
             "windSpeed",
-            format!("Speed of wind at 10m above ground in {}", wind_unit),
+            "Speed of wind at 10m above ground in",
+            unit_symbol(hours, |p| p.wind.wind_speed),
         ),
-        ("gust", format!("Wind gust in {}", wind_unit)),
+        ("gust", "Wind gust in", unit_symbol(hours, |p| p.wind.gust)),
         (
             "airTemperature",
-            "Air temperature in degrees celsius".to_string(),
+            "Air temperature in",
+            unit_symbol(hours, |p| p.air_temperature),
         ),
-        ("swellHeight", "Height of swell waves in meters".to_string()),
         (
-            "swellPeriod",
-            "Period of swell waves in seconds".to_string(),
+            "swellHeight",
+            "Height of swell waves in",
+            unit_symbol(hours, |p| p.waves.swell_height),
         ),
         (
-            "swellDirection",
-            "Direction of swell waves. 0° indicates swell coming from north".to_string(),
+            "swellPeriod",
+            "Period of swell waves in",
+            unit_symbol(hours, |p| p.waves.swell_period),
         ),
         (
             "waterTemperature",
-            "Water temperature in degrees celsius".to_string(),
+            "Water temperature in",
+            unit_symbol(hours, |p| p.water_temperature),
         ),
         (
-            "windDirection",
+            "tideHeight",
+            "Sea level relative to mean sea level in",
+            unit_symbol(hours, |p| p.tide_height),
+        ),
+    ];
+
+    let mut units: HashMap<String, String> = typed
+        .iter()
+        .filter_map(|(key, description, symbol)| {
+            symbol.map(|s| (key.to_string(), format!("{} {}", description, s)))
+        })
+        .collect();
+
+    if unit_symbol(hours, |p| p.waves.swell_direction).is_some() {
+        units.insert(
+            "swellDirection".to_string(),
+            "Direction of swell waves. 0° indicates swell coming from north".to_string(),
+        );
+    }
+    if unit_symbol(hours, |p| p.wind.wind_direction).is_some() {
+        units.insert(
+            "windDirection".to_string(),
             "Direction of wind at 10m above ground. 0° indicates wind coming from north"
                 .to_string(),
-        ),
-        ("cloudCover", "Total cloud coverage in percent".to_string()),
-        (
-            "precipitation",
-            "TMean precipitation in kg/m²/h = mm/h".to_string(),
-        ),
-    ]
-    .iter()
-    .map(|(k, v)| (k.to_string(), v.clone()))
-    .collect()
+        );
+    }
+    if hours.iter().any(|p| p.wind.shore_wind.is_some()) {
+        units.insert(
+            "shoreWind".to_string(),
+            "Wind direction relative to the shoreline: onshore, cross-onshore, cross, \
+             cross-offshore or offshore"
+                .to_string(),
+        );
+    }
+    if hours.iter().any(|p| p.is_daylight.is_some()) {
+        units.insert(
+            "isDaylight".to_string(),
+            "Whether the hour is between sunrise and sunset (see meta.sun)".to_string(),
+        );
+    }
+    if hours.iter().any(|p| p.sailability.is_some()) {
+        units.insert(
+            "sailability".to_string(),
+            "Score 0-100 against the [rider] thresholds; 0 with the violated thresholds as issues"
+                .to_string(),
+        );
+    }
+    if hours.iter().any(|p| p.gear.is_some()) {
+        units.insert(
+            "gear".to_string(),
+            "Recommended sail size in m² (with fit against the ideal size) and board".to_string(),
+        );
+    }
+    units.insert(
+        "cloudCover".to_string(),
+        "Total cloud coverage in percent".to_string(),
+    );
+    units.insert(
+        "precipitation".to_string(),
+        "Mean precipitation in kg/m²/h = mm/h".to_string(),
+    );
+
+    units
 }
 
 fn create_meta(
     lat: f64,
     lng: f64,
-    start: DateTime<Utc>,
-    end: DateTime<Utc>,
+    window: &ForecastWindow,
     provider_name: &str,
+    hours: &[WeatherDataPoint],
     tz: Tz,
 ) -> TransformedMetaData {
     let now = tz.from_utc_datetime(&Utc::now().naive_utc());
@@ -102,11 +201,62 @@ fn create_meta(
     TransformedMetaData {
         lat,
         lng,
-        start: start.to_rfc3339(),
-        end: end.to_rfc3339(),
+        start: window.start.with_timezone(&tz).to_rfc3339(),
+        end: window.end.with_timezone(&tz).to_rfc3339(),
         report_generated_at: report_time,
         provider: provider_name.to_string(),
-        units: create_units_map(provider_name),
+        units: create_units_map(hours),
+        sun: window
+            .local_dates(tz)
+            .into_iter()
+            .map(|date| sun::sun_day(date, lat, lng, tz))
+            .collect(),
+    }
+}
+
+fn create_ensemble_meta(
+    config: &ResolvedConfig,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    providers: Vec<String>,
+) -> EnsembleMetaData {
+    let now = config.timezone.from_utc_datetime(&Utc::now().naive_utc());
+
+    let thresholds = [
+        ("windSpeedSpread", config.ensemble.wind_speed_spread),
+        ("gustSpread", config.ensemble.gust_spread),
+        ("swellHeightSpread", config.ensemble.swell_height_spread),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), *v))
+    .collect();
+
+    let wind = config.units.wind.symbol();
+    let height = config.units.height.symbol();
+    let units = [
+        ("windSpeed", format!("Wind speed statistics across providers in {}", wind)),
+        ("gust", format!("Wind gust statistics across providers in {}", wind)),
+        ("swellHeight", format!("Swell height statistics across providers in {}", height)),
+        ("stdDev", "Population standard deviation across providers".to_string()),
+        (
+            "disagreement",
+            "Quantities whose spread (max - min) exceeds its threshold (thresholds in m/s and m)"
+                .to_string(),
+        ),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.clone()))
+    .collect();
+
+    EnsembleMetaData {
+        lat: config.lat,
+        lng: config.lng,
+        start: start.with_timezone(&config.timezone).to_rfc3339(),
+        end: end.with_timezone(&config.timezone).to_rfc3339(),
+        report_generated_at: now.format("%Y-%m-%d %H:%M").to_string(),
+        providers,
+        thresholds,
+        units,
     }
 }
 
@@ -114,13 +264,184 @@ fn create_meta(
 // Utility Functions
 // ============================================================================
 
-fn write_weather_json(data: &TransformedWeatherResponse, filename: &str) -> Result<()> {
+fn write_weather_json<T: Serialize>(data: &T, filename: &str) -> Result<()> {
     println!("Writing weather data to file: {}", filename);
     let json = serde_json::to_string_pretty(data)?;
     fs::write(filename, json)?;
     Ok(())
 }
 
+/// Write the report to `{stem}.{extension}` and return the file name
+///
+/// Formats that have no room for the metadata get a `{stem}.meta.json`
+/// sidecar with the `meta` block. Terminal formats are printed instead and
+/// return `None`.
+fn write_report(
+    data: &TransformedWeatherResponse,
+    stem: &str,
+    format: OutputFormat,
+    resolved_config: &ResolvedConfig,
+    window: &ForecastWindow,
+) -> Result<Option<String>> {
+    if format.prints_to_stdout() {
+        print!("{}", format.render(data, resolved_config, window)?);
+        return Ok(None);
+    }
+
+    let filename = format!("{}.{}", stem, format.extension());
+    println!("Writing weather data to file: {}", filename);
+    fs::write(&filename, format.render(data, resolved_config, window)?)?;
+
+    if format.has_meta_sidecar() {
+        write_weather_json(&data.meta, &format!("{}.meta.json", stem))?;
+    }
+    Ok(Some(filename))
+}
+
+/// `{prefix}_{start date}`, or just `{prefix}` for formats with a fixed
+/// file name
+fn report_stem(prefix: String, format: OutputFormat, window: &ForecastWindow, tz: Tz) -> String {
+    if format.has_fixed_file_name() {
+        prefix
+    } else {
+        format!("{}_{}", prefix, window.start.with_timezone(&tz).format("%y%m%d"))
+    }
+}
+
+/// Spot name made safe for use in a file name
+fn file_name_part(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Fill tide heights and events from the spot's harmonic constants
+///
+/// Values a provider already returned are kept; the prediction is only used
+/// to report how far the provider's hourly sea level is from it.
+fn predict_tides(weather_data: &mut WeatherData, resolved_config: &ResolvedConfig, window: &ForecastWindow) {
+    let Some(table) = resolved_config
+        .spot
+        .as_ref()
+        .and_then(|spot| spot.tide_table.as_ref())
+    else {
+        return;
+    };
+
+    let mut differences = Vec::new();
+    for point in weather_data.data_points.iter_mut() {
+        let predicted = table.predict(point.time.utc());
+        match point.tide_height {
+            Some(reported) => differences.push((reported.value() - predicted.value()).abs()),
+            None => point.tide_height = Some(predicted),
+        }
+    }
+    if weather_data.tides.is_empty() {
+        weather_data.tides = table.extremes(window.start, window.end, resolved_config.timezone);
+    }
+
+    if let Some(max) = differences.iter().copied().reduce(f64::max) {
+        let mean = differences.iter().sum::<f64>() / differences.len() as f64;
+        eprintln!(
+            "Tide check: provider sea level differs from the harmonic prediction by {:.2} m on average (max {:.2} m)",
+            mean, max
+        );
+    }
+}
+
+/// Convert units and add the fields derived from the location and the spot
+fn prepare_points(weather_data: &mut WeatherData, resolved_config: &ResolvedConfig) {
+    let shore_bearing = resolved_config
+        .spot
+        .as_ref()
+        .and_then(|spot| spot.config.shore_bearing);
+
+    for tide in weather_data.tides.iter_mut() {
+        tide.height = tide.height.to(resolved_config.units.height);
+    }
+
+    for point in weather_data.data_points.iter_mut() {
+        point.is_daylight = Some(sun::is_daylight(
+            point.time.utc(),
+            resolved_config.lat,
+            resolved_config.lng,
+            resolved_config.timezone,
+        ));
+        if let Some(bearing) = shore_bearing {
+            point.classify_shore_wind(bearing);
+        }
+        point.sailability = Some(sessions::score_hour(point, &resolved_config.rider));
+        point.gear = point
+            .wind
+            .wind_speed
+            .and_then(|wind| gear::recommend(wind, point.wind.gust, &resolved_config.rider));
+        point.convert_units(&resolved_config.units);
+    }
+}
+
+/// Assemble the output file contents from prepared data points
+///
+/// Session windows are found before `drop_night` removes the night hours, so
+/// an evening and the next morning never merge into one window.
+fn build_response(
+    weather_data: WeatherData,
+    resolved_config: &ResolvedConfig,
+    window: &ForecastWindow,
+    provider_name: &str,
+    drop_night: bool,
+) -> TransformedWeatherResponse {
+    let sessions = sessions::find_session_windows(&weather_data.data_points);
+    let mut hours = weather_data.data_points;
+    if drop_night {
+        hours.retain(|point| point.is_daylight != Some(false));
+    }
+
+    let meta = create_meta(
+        resolved_config.lat,
+        resolved_config.lng,
+        window,
+        provider_name,
+        &hours,
+        resolved_config.timezone,
+    );
+    TransformedWeatherResponse {
+        hours,
+        sessions,
+        tides: weather_data.tides,
+        alerts: weather_data.alerts.unwrap_or_default(),
+        meta,
+    }
+}
+
+/// Print one line per session window, or a note that there are none
+fn print_sessions(sessions: &[SessionWindow]) {
+    if sessions.is_empty() {
+        println!("No session windows match the [rider] thresholds.");
+        return;
+    }
+
+    println!("Session windows:");
+    for session in sessions {
+        let start = session.start.local();
+        let end = session.end.local();
+        let gear = session.gear.as_ref().map_or(String::new(), |gear| {
+            let board = gear.board.as_ref().map_or(String::new(), |b| format!(" on {}", b));
+            format!(", rig {:.1} m²{}", gear.sail_size, board)
+        });
+        println!(
+            "  {} {} - {} ({}h, score {}), peak {} at {}{}",
+            start.format("%a %d %b"),
+            start.format("%H:%M"),
+            end.format("%H:%M"),
+            session.hours,
+            session.score,
+            session.peak.wind_speed,
+            session.peak.time.local().format("%H:%M"),
+            gear
+        );
+    }
+}
+
 fn print_error(error_type: &str, message: &str) {
     eprintln!("\n{}", "=".repeat(70));
     eprintln!("{}", error_type);
@@ -151,7 +472,13 @@ async fn run() -> Result<()> {
         for (name, description) in provider_registry::all_provider_descriptions() {
             let metadata = provider_registry::get_provider_metadata(name).unwrap();
             println!("  {} - {}", name, description);
-            println!("    API Key: {}", metadata.api_key_var);
+            println!(
+                "    API Key: {}",
+                metadata.api_key_var.unwrap_or("not required")
+            );
+            if let (Some(base_url), Some(var)) = (metadata.base_url, metadata.base_url_var) {
+                println!("    API URL: {} (override with {})", base_url, var);
+            }
             println!();
         }
         return Ok(());
@@ -179,6 +506,10 @@ async fn run() -> Result<()> {
 
     validate_args(&args)?;
 
+    if args.is_multi_spot() {
+        return run_batch(&args).await;
+    }
+
     let resolved_config = config::resolve_from_args_and_file(&args)?;
 
     eprintln!("\n{}", resolved_config);
@@ -197,16 +528,26 @@ async fn run() -> Result<()> {
         resolved_config.lng,
     );
 
-    let provider = provider_registry::create_provider(&resolved_config.provider)?;
-
-    let now = Utc::now();
-    let start = day_start_utc(now, resolved_config.first_day_offset as i64);
-    let end = day_end_utc(
-        now,
-        (resolved_config.first_day_offset + resolved_config.days_ahead - 1) as i64,
+    let window = ForecastWindow::local_days(
+        forecast_now(&args)?,
+        resolved_config.first_day_offset as u32,
+        resolved_config.days_ahead as u32,
+        resolved_config.timezone,
     );
+    let (start, end) = (window.start, window.end);
+
+    if args.ensemble {
+        run_ensemble(&resolved_config, start, end).await?;
+        if args.save {
+            config::save_config_from_resolved(&resolved_config, args.config_file_path.as_ref())?;
+        }
+        return Ok(());
+    }
 
-    let weather_data = provider
+    let provider =
+        provider_registry::create_provider(&resolved_config.provider, &resolved_config)?;
+
+    let mut weather_data = provider
         .fetch_weather_data(
             start,
             end,
@@ -216,38 +557,302 @@ async fn run() -> Result<()> {
         )
         .await?;
 
-    let transformed_data = TransformedWeatherResponse {
-        hours: weather_data.data_points,
-        meta: create_meta(
-            resolved_config.lat,
-            resolved_config.lng,
+    window.trim(&mut weather_data);
+    predict_tides(&mut weather_data, &resolved_config, &window);
+    prepare_points(&mut weather_data, &resolved_config);
+
+    let transformed_data = build_response(
+        weather_data,
+        &resolved_config,
+        &window,
+        provider.name(),
+        args.drop_night,
+    );
+
+    // Like a batch run, a spot run names the spot so that spots with a
+    // fixed file name (`.ics`) do not overwrite each other
+    let spot_part = resolved_config
+        .spot
+        .as_ref()
+        .map(|spot| format!("{}_", file_name_part(&spot.name)))
+        .unwrap_or_default();
+    let stem = report_stem(
+        format!(
+            "weather_data_{}{}_{}d",
+            spot_part,
+            provider.short_name(),
+            resolved_config.days_ahead
+        ),
+        args.format,
+        &window,
+        resolved_config.timezone,
+    );
+
+    write_report(&transformed_data, &stem, args.format, &resolved_config, &window)?;
+    println!(
+        "Loaded {} hourly data points from file.",
+        transformed_data.hours.len()
+    );
+    print_sessions(&transformed_data.sessions);
+
+    if args.save {
+        config::save_config_from_resolved(&resolved_config, args.config_file_path.as_ref())?;
+    }
+
+    Ok(())
+}
+
+/// Fetch every ensemble member concurrently and write the spread statistics
+///
+/// Without an explicit `[ensemble] providers` list, every registered provider
+/// that can be instantiated takes part; providers missing an API key are
+/// skipped. Members whose fetch fails are reported and left out.
+async fn run_ensemble(
+    resolved_config: &ResolvedConfig,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<()> {
+    let explicit = !resolved_config.ensemble.providers.is_empty();
+    let names: Vec<String> = if explicit {
+        resolved_config.ensemble.providers.clone()
+    } else {
+        // "composite" only re-mixes other members, and "openmeteo-marine"
+        // already carries the "openmeteo" atmosphere, so counting both would
+        // double-weight Open-Meteo.
+        provider_registry::all_provider_names()
+            .filter(|name| !matches!(*name, "composite" | "openmeteo"))
+            .map(String::from)
+            .collect()
+    };
+
+    let mut providers = Vec::new();
+    for name in &names {
+        match provider_registry::create_provider(name, resolved_config) {
+            Ok(provider) => providers.push(provider),
+            Err(e) if !explicit => eprintln!("Skipping '{}' in ensemble: {:#}", name, e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let results = futures::future::join_all(providers.iter().map(|provider| {
+        provider.fetch_weather_data(
             start,
             end,
-            provider.name(),
+            resolved_config.lat,
+            resolved_config.lng,
             resolved_config.timezone,
-        ),
+        )
+    }))
+    .await;
+
+    let mut members = Vec::new();
+    for (provider, result) in providers.iter().zip(results) {
+        match result {
+            Ok(data) => members.push((provider.name().to_string(), data)),
+            Err(e) => eprintln!(
+                "⚠️  '{}' failed and is excluded from the ensemble: {:#}",
+                provider.name(),
+                e
+            ),
+        }
+    }
+
+    if members.len() < 2 {
+        anyhow::bail!(
+            "Ensemble needs at least 2 providers with data, got {}.\n\
+             Configure API keys for more providers or list them under [ensemble] in the config file.",
+            members.len()
+        );
+    }
+
+    let hours = ensemble::build_ensemble(
+        &members,
+        &resolved_config.ensemble,
+        &resolved_config.units,
+        start,
+        end,
+        resolved_config.timezone,
+    )?;
+
+    let provider_names = members.into_iter().map(|(name, _)| name).collect();
+    let response = EnsembleWeatherResponse {
+        meta: create_ensemble_meta(resolved_config, start, end, provider_names),
+        hours,
     };
 
     let filename = format!(
-        "weather_data_{}_{}d_{}.json",
-        provider.short_name(),
+        "weather_data_ensemble_{}d_{}.json",
         resolved_config.days_ahead,
-        start.format("%y%m%d")
+        start.with_timezone(&resolved_config.timezone).format("%y%m%d")
     );
 
-    write_weather_json(&transformed_data, &filename)?;
+    write_weather_json(&response, &filename)?;
+    let disagreements = response
+        .hours
+        .iter()
+        .filter(|hour| !hour.disagreement.is_empty())
+        .count();
     println!(
-        "Loaded {} hourly data points from file.",
-        transformed_data.hours.len()
+        "Ensemble of {} providers: {} hourly data points, {} with provider disagreement.",
+        response.meta.providers.len(),
+        response.hours.len(),
+        disagreements
     );
 
-    if args.save {
-        config::save_config_from_resolved(&resolved_config, args.config_file_path.as_ref())?;
+    Ok(())
+}
+
+/// The moment the forecast window is counted from: now, or when the
+/// replayed fixtures were recorded so the same days are requested
+fn forecast_now(args: &Args) -> Result<DateTime<Utc>> {
+    match &args.replay {
+        Some(dir) => http::replay_time(dir),
+        None => Ok(Utc::now()),
+    }
+}
+
+/// Fetch several spots concurrently, write one file per spot and a ranked index
+///
+/// Spots whose provider cannot be created or whose fetch fails are reported
+/// and left out of the index.
+async fn run_batch(args: &Args) -> Result<()> {
+    let configs = config::resolve_spots_from_args_and_file(args)?;
+    let now = forecast_now(args)?;
+
+    let mut jobs = Vec::new();
+    for resolved_config in &configs {
+        eprintln!("\n{}", resolved_config);
+        check_timezone_match(
+            resolved_config.timezone,
+            resolved_config.lat,
+            resolved_config.lng,
+        );
+
+        let spot = spot_name(resolved_config);
+        match provider_registry::create_provider(&resolved_config.provider, resolved_config) {
+            Ok(provider) => {
+                let window = ForecastWindow::local_days(
+                    now,
+                    resolved_config.first_day_offset as u32,
+                    resolved_config.days_ahead as u32,
+                    resolved_config.timezone,
+                );
+                jobs.push((resolved_config, provider, window));
+            }
+            Err(e) => eprintln!("⚠️  Skipping spot '{}': {:#}", spot, e),
+        }
+    }
+
+    let results = futures::future::join_all(jobs.iter().map(|(resolved_config, provider, window)| {
+        provider.fetch_weather_data(
+            window.start,
+            window.end,
+            resolved_config.lat,
+            resolved_config.lng,
+            resolved_config.timezone,
+        )
+    }))
+    .await;
+
+    let mut rankings = Vec::new();
+    for ((resolved_config, provider, window), result) in jobs.iter().zip(results) {
+        let spot = spot_name(resolved_config);
+        let mut weather_data = match result {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("⚠️  '{}' failed for spot '{}': {:#}", provider.name(), spot, e);
+                continue;
+            }
+        };
+
+        window.trim(&mut weather_data);
+        predict_tides(&mut weather_data, resolved_config, window);
+        prepare_points(&mut weather_data, resolved_config);
+
+        let stem = report_stem(
+            format!(
+                "weather_data_{}_{}_{}d",
+                file_name_part(spot),
+                provider.short_name(),
+                resolved_config.days_ahead
+            ),
+            args.format,
+            window,
+            resolved_config.timezone,
+        );
+        let transformed_data = build_response(
+            weather_data,
+            resolved_config,
+            window,
+            provider.name(),
+            args.drop_night,
+        );
+        // Terminal formats are rejected for several spots by validate_args
+        let filename = write_report(&transformed_data, &stem, args.format, resolved_config, window)?
+            .unwrap_or_default();
+        rankings.push(SpotRanking::from_hours(
+            spot,
+            provider.name(),
+            &filename,
+            &transformed_data.hours,
+        ));
+    }
+
+    if rankings.is_empty() {
+        anyhow::bail!("None of the {} spots returned forecast data.", configs.len());
+    }
+
+    // The index is dated in the first spot's timezone; spots are normally in
+    // one region, so this matches every spot's file name.
+    let first = &configs[0];
+    let local_now = first.timezone.from_utc_datetime(&now.naive_utc());
+    let index = SpotIndexResponse {
+        spots: batch::rank_spots(rankings),
+        meta: SpotIndexMetaData {
+            report_generated_at: local_now.format("%Y-%m-%d %H:%M").to_string(),
+            days_ahead: first.days_ahead,
+            first_day_offset: first.first_day_offset,
+            criterion: format!(
+                "Mean wind speed over the window in {}, highest first; peak wind speed breaks ties",
+                first.units.wind.symbol()
+            ),
+        },
+    };
+
+    let first_window = ForecastWindow::local_days(
+        now,
+        first.first_day_offset as u32,
+        first.days_ahead as u32,
+        first.timezone,
+    );
+    let filename = format!(
+        "weather_index_{}d_{}.json",
+        first.days_ahead,
+        first_window.start.with_timezone(&first.timezone).format("%y%m%d")
+    );
+    write_weather_json(&index, &filename)?;
+
+    println!("Ranked {} of {} spots:", index.spots.len(), configs.len());
+    for entry in &index.spots {
+        let mean = entry
+            .mean_wind_speed
+            .map_or("no wind data".to_string(), |v| {
+                format!("{:.1} {}", v, first.units.wind.symbol())
+            });
+        println!("  {}. {} ({}) - {}", entry.rank, entry.spot, entry.provider, mean);
     }
 
     Ok(())
 }
 
+/// Name of the spot a batch configuration was resolved for
+fn spot_name(resolved_config: &ResolvedConfig) -> &str {
+    resolved_config
+        .spot
+        .as_ref()
+        .map_or("", |spot| spot.name.as_str())
+}
+
 fn load_env(args: &Args) {
     if let Some(env_path) = args.env_file.as_ref() {
         dotenv::from_path(env_path).ok();
@@ -255,21 +860,3 @@ fn load_env(args: &Args) {
         dotenv::dotenv().ok();
     }
 }
-
-/// Creates a UTC datetime at the start of day (00:00:00) for a date offset from now
-fn day_start_utc(now: DateTime<Utc>, day_offset: i64) -> DateTime<Utc> {
-    let date = (now + chrono::Duration::days(day_offset))
-        .date_naive()
-        .and_hms_opt(0, 0, 0)
-        .unwrap();
-    Utc.from_utc_datetime(&date)
-}
-
-/// Creates a UTC datetime at the end of day (23:59:59) for a date offset from now
-fn day_end_utc(now: DateTime<Utc>, day_offset: i64) -> DateTime<Utc> {
-    let date = (now + chrono::Duration::days(day_offset))
-        .date_naive()
-        .and_hms_opt(23, 59, 59)
-        .unwrap();
-    Utc.from_utc_datetime(&date)
-}
@@ -4,6 +4,7 @@
 //! Providers self-register using the `inventory::submit!()` macro.
 
 use anyhow::{anyhow, Result};
+use crate::config::ResolvedConfig;
 use crate::forecast_provider::ForecastProvider;
 use std::collections::HashMap;
 
@@ -19,18 +20,29 @@ pub struct ProviderMetadata {
     
     /// Environment variable name for the provider's API key
     /// (e.g., "STORMGLASS_API_KEY", "OPEN_WEATHER_MAP_API_KEY")
-    pub api_key_var: &'static str,
+    /// `None` for keyless providers such as Open-Meteo
+    pub api_key_var: Option<&'static str>,
+
+    /// Default API root the provider appends its endpoint paths to,
+    /// overridable through the `[endpoints]` config section or `base_url_var`
+    /// `None` for providers that make no requests of their own (composite)
+    pub base_url: Option<&'static str>,
+
+    /// Environment variable that overrides the API root
+    /// (e.g., "STORMGLASS_API_URL"); takes precedence over the config file
+    pub base_url_var: Option<&'static str>,
     
     /// Factory function that creates an instance of this provider
     /// 
     /// The function is responsible for:
-    /// - Retrieving its own API key from environment
+    /// - Retrieving its own API key from environment (if it needs one)
+    /// - Reading any provider-specific settings from the resolved config
     /// - Constructing the provider instance
     /// - Returning appropriate errors if configuration is missing
     /// 
     /// # Errors
     /// Returns error if API key is not set or provider initialization fails
-    pub instantiate: fn() -> Result<Box<dyn ForecastProvider>>,
+    pub instantiate: fn(&ResolvedConfig) -> Result<Box<dyn ForecastProvider>>,
 }
 
 // Enable inventory collection of ProviderMetadata
@@ -55,9 +67,9 @@ pub fn all_provider_descriptions() -> impl Iterator<Item = (&'static str, &'stat
 }
 
 
-pub fn create_provider(name: &str) -> Result<Box<dyn ForecastProvider>> {
+pub fn create_provider(name: &str, config: &ResolvedConfig) -> Result<Box<dyn ForecastProvider>> {
     match get_provider_metadata(name) {
-        Some(meta) => (meta.instantiate)(),
+        Some(meta) => (meta.instantiate)(config),
         None => {
             let available: Vec<_> = all_provider_names().collect();
             Err(anyhow!(
@@ -104,8 +116,8 @@ pub fn check_duplicates() {
                 "FATAL: Duplicate provider name '{}' detected in registry!\n\
                 This indicates a programming error where two provider modules \
                 registered with the same name.\n\
-                First registration: {} ({})\n\
-                Second registration: {} ({})",
+                First registration: {} ({:?})\n\
+                Second registration: {} ({:?})",
                 meta.name,
                 existing.description,
                 existing.api_key_var,
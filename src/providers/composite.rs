@@ -0,0 +1,257 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use futures::future::try_join_all;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::loader::CompositeConfig;
+use crate::forecast_provider::{ForecastProvider, WeatherAlert, WeatherData, WeatherDataPoint};
+use crate::provider_registry::{self, ProviderMetadata};
+
+// ============================================================================
+// Composite Provider
+// ============================================================================
+
+/// Merges sections from several providers into one forecast
+///
+/// The `[composite]` config section maps each section of [`WeatherDataPoint`]
+/// to a provider. All referenced providers are fetched concurrently and their
+/// hours are matched by UTC timestamp. Every hour returned by any source is
+/// kept; sections whose provider has no data for that hour stay empty.
+pub struct CompositeProvider {
+    name: String,
+    short_name: String,
+    mapping: CompositeConfig,
+    sources: Vec<Box<dyn ForecastProvider>>,
+}
+
+impl CompositeProvider {
+    pub fn new(mapping: CompositeConfig, sources: Vec<Box<dyn ForecastProvider>>) -> Self {
+        Self {
+            name: "composite".to_string(),
+            short_name: "mix".to_string(),
+            mapping,
+            sources,
+        }
+    }
+
+    /// Build one data point per distinct UTC hour, taking each section from
+    /// the provider it is mapped to
+    fn merge(mapping: &CompositeConfig, results: &[(&str, WeatherData)]) -> WeatherData {
+        let mut hours: BTreeMap<DateTime<Utc>, WeatherDataPoint> = BTreeMap::new();
+        let mut by_provider: HashMap<&str, HashMap<DateTime<Utc>, &WeatherDataPoint>> =
+            HashMap::new();
+
+        for (name, data) in results {
+            let index = by_provider.entry(name).or_default();
+            for point in &data.data_points {
+                let utc = point.time.utc();
+                index.insert(utc, point);
+                hours
+                    .entry(utc)
+                    .or_insert_with(|| WeatherDataPoint::empty(point.time.clone()));
+            }
+        }
+
+        let source = |provider: &Option<String>, utc: &DateTime<Utc>| {
+            provider
+                .as_deref()
+                .and_then(|name| by_provider.get(name))
+                .and_then(|index| index.get(utc))
+                .copied()
+        };
+
+        // Tides are not a mapped section; they come from the first source
+        // that was asked for them
+        let tide_source = results
+            .iter()
+            .find(|(_, data)| {
+                !data.tides.is_empty() || data.data_points.iter().any(|p| p.tide_height.is_some())
+            })
+            .map(|(name, data)| (name.to_string(), data.tides.clone()));
+        let (tide_provider, tides) = tide_source.unzip();
+
+        for (utc, point) in hours.iter_mut() {
+            if let Some(p) = source(&mapping.wind, utc) {
+                point.wind = p.wind.clone();
+            }
+            if let Some(p) = source(&mapping.waves, utc) {
+                point.waves = p.waves.clone();
+            }
+            if let Some(p) = source(&mapping.clouds, utc) {
+                point.clouds = p.clouds.clone();
+            }
+            if let Some(p) = source(&mapping.air_temperature, utc) {
+                point.air_temperature = p.air_temperature;
+            }
+            if let Some(p) = source(&mapping.water_temperature, utc) {
+                point.water_temperature = p.water_temperature;
+            }
+            if let Some(p) = source(&mapping.precipitation, utc) {
+                point.precipitation = p.precipitation;
+            }
+            if let Some(p) = source(&tide_provider, utc) {
+                point.tide_height = p.tide_height;
+            }
+        }
+
+        let alerts: Vec<WeatherAlert> = results
+            .iter()
+            .filter_map(|(_, data)| data.alerts.as_ref())
+            .flatten()
+            .cloned()
+            .collect();
+
+        WeatherData {
+            data_points: hours.into_values().collect(),
+            alerts: if alerts.is_empty() { None } else { Some(alerts) },
+            tides: tides.unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for CompositeProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    async fn fetch_weather_data(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        lat: f64,
+        lng: f64,
+        target_tz: Tz,
+    ) -> Result<WeatherData> {
+        // Execute all source requests in parallel
+        let results = try_join_all(self.sources.iter().map(|source| async move {
+            let data = source
+                .fetch_weather_data(start, end, lat, lng, target_tz)
+                .await
+                .with_context(|| format!("Composite source '{}' failed", source.name()))?;
+            Ok::<_, anyhow::Error>((source.name(), data))
+        }))
+        .await?;
+
+        Ok(Self::merge(&self.mapping, &results))
+    }
+}
+
+// ============================================================================
+// Provider Registry
+// ============================================================================
+
+// Register provider with central registry
+inventory::submit! {
+    ProviderMetadata {
+        name: "composite",
+        description: "Merge fields from several providers ([composite] section in config)",
+        api_key_var: None,
+        base_url: None,
+        base_url_var: None,
+        instantiate: |config| {
+            let sources = config
+                .composite
+                .providers()
+                .into_iter()
+                .map(|name| provider_registry::create_provider(name, config))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(CompositeProvider::new(config.composite.clone(), sources)))
+        },
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::{convert_timezone, UtcTimestamp};
+    use crate::units::{Length, LengthUnit, Speed, SpeedUnit, Temperature, TemperatureUnit};
+
+    fn ms(value: f64) -> Option<Speed> {
+        Some(Speed::new(value, SpeedUnit::MetersPerSecond))
+    }
+
+    fn meters(value: f64) -> Option<Length> {
+        Some(Length::new(value, LengthUnit::Meters))
+    }
+
+    fn celsius(value: f64) -> Option<Temperature> {
+        Some(Temperature::new(value, TemperatureUnit::Celsius))
+    }
+
+    fn point(ts: i64, tz: Tz) -> WeatherDataPoint {
+        let utc = UtcTimestamp(DateTime::<Utc>::from_timestamp(ts, 0).unwrap());
+        WeatherDataPoint::empty(convert_timezone(utc, tz).unwrap())
+    }
+
+    #[test]
+    fn test_merge_takes_each_section_from_its_mapped_provider() {
+        let mapping = CompositeConfig {
+            wind: Some("windy".to_string()),
+            waves: Some("stormglass".to_string()),
+            air_temperature: Some("stormglass".to_string()),
+            ..Default::default()
+        };
+
+        let mut w = point(3600, chrono_tz::UTC);
+        w.wind.wind_speed = ms(12.0);
+        w.waves.swell_height = meters(9.9);
+        w.air_temperature = celsius(30.0);
+
+        let mut s = point(3600, "Asia/Jerusalem".parse().unwrap());
+        s.wind.wind_speed = ms(1.0);
+        s.waves.swell_height = meters(1.5);
+        s.air_temperature = celsius(21.0);
+        s.tide_height = meters(0.3);
+        let mut s_later = point(7200, chrono_tz::UTC);
+        s_later.waves.swell_height = meters(1.6);
+
+        let alert = WeatherAlert {
+            sender: "IMS".to_string(),
+            event: "Strong Wind Warning".to_string(),
+            start: s.time.clone(),
+            end: s_later.time.clone(),
+            description: String::new(),
+            severity: None,
+        };
+
+        let results = vec![
+            (
+                "windy",
+                WeatherData { data_points: vec![w], alerts: None, tides: Vec::new() },
+            ),
+            (
+                "stormglass",
+                WeatherData {
+                    data_points: vec![s_later, s],
+                    alerts: Some(vec![alert.clone()]),
+                    tides: Vec::new(),
+                },
+            ),
+        ];
+
+        let merged = CompositeProvider::merge(&mapping, &results);
+        assert_eq!(merged.data_points.len(), 2, "hours are the union of all sources");
+
+        let first = &merged.data_points[0];
+        assert_eq!(first.time.utc().timestamp(), 3600);
+        assert_eq!(first.wind.wind_speed, ms(12.0));
+        assert_eq!(first.waves.swell_height, meters(1.5));
+        assert_eq!(first.air_temperature, celsius(21.0));
+        assert_eq!(first.tide_height, meters(0.3), "tides come from the source that has them");
+
+        let second = &merged.data_points[1];
+        assert_eq!(second.wind.wind_speed, None, "windy has no data for this hour");
+        assert_eq!(second.waves.swell_height, meters(1.6));
+
+        assert_eq!(merged.alerts, Some(vec![alert]));
+    }
+}
@@ -1,3 +1,6 @@
 pub mod stormglass;
 pub mod openweathermap;
-pub mod windy;
\ No newline at end of file
+pub mod windy;
+pub mod openmeteo;
+pub mod openmeteo_marine;
+pub mod composite;
\ No newline at end of file
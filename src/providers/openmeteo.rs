@@ -0,0 +1,314 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::forecast_provider::{
+    convert_timezone, CloudDatapointSection, ForecastProvider, UtcTimestamp,
+    WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection,
+};
+use crate::http::{CacheKey, HttpClient};
+use crate::provider_registry::ProviderMetadata;
+use crate::units::{degrees, Speed, SpeedUnit, Temperature, TemperatureUnit};
+
+// ============================================================================
+// Custom Error Types
+// ============================================================================
+
+#[derive(Error, Debug)]
+#[error("Open-Meteo API Error (HTTP {status_code}): {message}")]
+pub struct OpenMeteoAPIError {
+    status_code: u16,
+    message: String,
+}
+
+impl OpenMeteoAPIError {
+    fn new(status_code: u16, message: String) -> Self {
+        Self {
+            status_code,
+            message,
+        }
+    }
+
+    /// Open-Meteo reports errors as `{"error": true, "reason": "..."}`
+    pub(crate) fn from_status_code_and_body(status_code: u16, body: &str) -> Self {
+        let reason = serde_json::from_str::<RawErrorResponse>(body)
+            .map(|e| e.reason)
+            .unwrap_or_else(|_| body.to_string());
+        let message = match status_code {
+            400 => format!("Bad Request: {}", reason),
+            429 => "Too Many Requests: The free API request limit was exceeded.\nPlease try again later.".to_string(),
+            _ => format!("Unexpected API error (HTTP {}): {}", status_code, reason),
+        };
+        Self::new(status_code, message)
+    }
+}
+
+// ============================================================================
+// Open-Meteo-Specific Data Structures
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RawErrorResponse {
+    reason: String,
+}
+
+/// Hourly variables are returned column-wise: one array per variable, all
+/// aligned with `time`. Individual values may be `null` at the forecast edges.
+#[derive(Debug, Deserialize)]
+struct RawHourlyData {
+    time: Vec<i64>,
+    temperature_2m: Option<Vec<Option<f64>>>,
+    wind_speed_10m: Option<Vec<Option<f64>>>,
+    wind_direction_10m: Option<Vec<Option<f64>>>,
+    wind_gusts_10m: Option<Vec<Option<f64>>>,
+    cloud_cover: Option<Vec<Option<f64>>>,
+    cloud_cover_low: Option<Vec<Option<f64>>>,
+    cloud_cover_mid: Option<Vec<Option<f64>>>,
+    cloud_cover_high: Option<Vec<Option<f64>>>,
+    precipitation: Option<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawWeatherResponse {
+    hourly: RawHourlyData,
+}
+
+/// Read row `i` of an hourly column, treating missing columns and `null`s alike
+pub(crate) fn value_at(column: &Option<Vec<Option<f64>>>, i: usize) -> Option<f64> {
+    column.as_ref().and_then(|v| v.get(i).copied().flatten())
+}
+
+// ============================================================================
+// Open-Meteo Provider
+// ============================================================================
+
+/// Overridden with `[endpoints] openmeteo` or `OPEN_METEO_API_URL`
+pub(crate) const DEFAULT_BASE_URL: &str = "https://api.open-meteo.com/v1";
+
+pub struct OpenMeteoProvider {
+    name: String,
+    short_name: String,
+    base_url: String,
+    http: HttpClient,
+}
+
+impl Default for OpenMeteoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenMeteoProvider {
+    pub fn new() -> Self {
+        Self {
+            name: "openmeteo".to_string(),
+            short_name: "om".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// API root the `/forecast` path is appended to
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Client used for all requests, e.g. one with a response cache
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    const HOURLY_PARAMS: [&'static str; 9] = [
+        "temperature_2m",
+        "wind_speed_10m",
+        "wind_direction_10m",
+        "wind_gusts_10m",
+        "cloud_cover",
+        "cloud_cover_low",
+        "cloud_cover_mid",
+        "cloud_cover_high",
+        "precipitation",
+    ];
+
+    fn transform_hourly(hourly: RawHourlyData, target_tz: Tz) -> Result<Vec<WeatherDataPoint>> {
+        let mut data_points = Vec::with_capacity(hourly.time.len());
+        for (i, &ts) in hourly.time.iter().enumerate() {
+            let utc_datetime = DateTime::<Utc>::from_timestamp(ts, 0)
+                .ok_or(anyhow::anyhow!("Invalid timestamp: {}", ts))?;
+            let local = convert_timezone(UtcTimestamp(utc_datetime), target_tz)?;
+
+            data_points.push(WeatherDataPoint {
+                time: local,
+                // Requested with wind_speed_unit=ms; temperatures default to °C
+                air_temperature: value_at(&hourly.temperature_2m, i)
+                    .map(|v| Temperature::new(v, TemperatureUnit::Celsius)),
+                wind: WindDatapoinSection {
+                    wind_speed: value_at(&hourly.wind_speed_10m, i)
+                        .map(|v| Speed::new(v, SpeedUnit::MetersPerSecond)),
+                    wind_direction: value_at(&hourly.wind_direction_10m, i).map(degrees),
+                    gust: value_at(&hourly.wind_gusts_10m, i)
+                        .map(|v| Speed::new(v, SpeedUnit::MetersPerSecond)),
+                    shore_wind: None,
+                },
+                waves: WaveDatapointSection {
+                    swell_height: None,
+                    swell_period: None,
+                    swell_direction: None,
+                    wind_wave_height: None,
+                    wind_wave_period: None,
+                    wind_wave_direction: None,
+                },
+                water_temperature: None,
+                clouds: CloudDatapointSection {
+                    cloud_cover: value_at(&hourly.cloud_cover, i),
+                    low_cloud_cover: value_at(&hourly.cloud_cover_low, i),
+                    medium_cloud_cover: value_at(&hourly.cloud_cover_mid, i),
+                    high_cloud_cover: value_at(&hourly.cloud_cover_high, i),
+                },
+                precipitation: value_at(&hourly.precipitation, i),
+                tide_height: None,
+                is_daylight: None,
+                sailability: None,
+                gear: None,
+            });
+        }
+        Ok(data_points)
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for OpenMeteoProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    async fn fetch_weather_data(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        lat: f64,
+        lng: f64,
+        target_tz: Tz,
+    ) -> Result<WeatherData> {
+        println!(
+            "Fetching weather data from {} to {} for coordinates ({}, {})",
+            start, end, lat, lng
+        );
+
+        let key = CacheKey { provider: &self.name, model: "forecast", lat, lng, start, end };
+        let request = self
+            .http
+            .get(&format!("{}/forecast", self.base_url))
+            .query(&[
+                ("latitude", lat.to_string()),
+                ("longitude", lng.to_string()),
+                ("hourly", Self::HOURLY_PARAMS.join(",")),
+                ("start_hour", start.format("%Y-%m-%dT%H:%M").to_string()),
+                ("end_hour", end.format("%Y-%m-%dT%H:%M").to_string()),
+                ("timezone", "GMT".to_string()),
+                ("timeformat", "unixtime".to_string()),
+                ("wind_speed_unit", "ms".to_string()),
+            ]);
+        let response = self
+            .http
+            .send(&key, request)
+            .await
+            .map_err(|e| e.with_context("Failed to connect to Open-Meteo API"))?;
+
+        let status = response.status;
+        let body = response.body;
+
+        if !status.is_success() {
+            return Err(OpenMeteoAPIError::from_status_code_and_body(status.as_u16(), &body).into());
+        }
+
+        let data: RawWeatherResponse =
+            serde_json::from_str(&body).context("Failed to parse API response")?;
+
+        let data_points = Self::transform_hourly(data.hourly, target_tz)?;
+
+        Ok(WeatherData {
+            data_points,
+            alerts: None,
+            tides: Vec::new(),
+        })
+    }
+}
+
+// ============================================================================
+// Provider Registry
+// ============================================================================
+
+// Register provider with central registry
+inventory::submit! {
+    ProviderMetadata {
+        name: "openmeteo",
+        description: "Open-Meteo Weather Forecast API (no API key required)",
+        api_key_var: None,
+        base_url: Some(DEFAULT_BASE_URL),
+        base_url_var: Some("OPEN_METEO_API_URL"),
+        instantiate: |config| {
+            Ok(Box::new(
+                OpenMeteoProvider::new()
+                    .with_base_url(config.endpoint("openmeteo").unwrap_or(DEFAULT_BASE_URL))
+                    .with_http(HttpClient::from_config(config)),
+            ))
+        },
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_transform_hourly_maps_columns_and_nulls() {
+        let raw: RawWeatherResponse = serde_json::from_value(json!({
+            "hourly": {
+                "time": [1765108800, 1765112400],
+                "temperature_2m": [22.5, null],
+                "wind_speed_10m": [5.2, 6.1],
+                "wind_direction_10m": [270.0, 280.0],
+                "wind_gusts_10m": [7.8, 8.2],
+                "cloud_cover_low": [10.0, 20.0],
+                "precipitation": [0.0, 0.4]
+            }
+        }))
+        .unwrap();
+
+        let points = OpenMeteoProvider::transform_hourly(raw.hourly, chrono_tz::UTC).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].air_temperature.map(|t| t.value()), Some(22.5));
+        assert_eq!(points[1].air_temperature, None);
+        assert_eq!(
+            points[1].wind.wind_speed,
+            Some(Speed::new(6.1, SpeedUnit::MetersPerSecond))
+        );
+        assert_eq!(points[0].wind.gust.map(|g| g.value()), Some(7.8));
+        assert_eq!(points[1].clouds.low_cloud_cover, Some(20.0));
+        assert_eq!(points[0].clouds.high_cloud_cover, None);
+        assert_eq!(points[1].precipitation, Some(0.4));
+        assert!(points[0].waves.swell_height.is_none());
+    }
+
+    #[test]
+    fn test_error_body_reason_is_surfaced() {
+        let err = OpenMeteoAPIError::from_status_code_and_body(
+            400,
+            r#"{"error":true,"reason":"Latitude must be in range of -90 to 90°."}"#,
+        );
+        assert!(err.to_string().contains("Latitude must be in range"));
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::forecast_provider::{
+    convert_timezone, CloudDatapointSection, ForecastProvider, UtcTimestamp,
+    WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection,
+};
+use crate::http::{CacheKey, HttpClient};
+use crate::provider_registry::ProviderMetadata;
+use crate::providers::openmeteo::{value_at, OpenMeteoAPIError, OpenMeteoProvider};
+use crate::units::{degrees, seconds, Length, LengthUnit, Temperature, TemperatureUnit};
+
+// ============================================================================
+// Open-Meteo Marine Data Structures
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RawMarineHourlyData {
+    time: Vec<i64>,
+    swell_wave_height: Option<Vec<Option<f64>>>,
+    swell_wave_period: Option<Vec<Option<f64>>>,
+    swell_wave_direction: Option<Vec<Option<f64>>>,
+    wind_wave_height: Option<Vec<Option<f64>>>,
+    wind_wave_period: Option<Vec<Option<f64>>>,
+    wind_wave_direction: Option<Vec<Option<f64>>>,
+    sea_surface_temperature: Option<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawMarineResponse {
+    hourly: RawMarineHourlyData,
+}
+
+// ============================================================================
+// Open-Meteo Marine Provider
+// ============================================================================
+
+/// Overridden with `[endpoints] openmeteo-marine` or `OPEN_METEO_MARINE_API_URL`
+const DEFAULT_BASE_URL: &str = "https://marine-api.open-meteo.com/v1";
+
+/// Waves and water temperature from the Open-Meteo Marine API, merged with
+/// the atmospheric fields (wind, air temperature, clouds, precipitation) of
+/// the regular Open-Meteo forecast for the same hours.
+pub struct OpenMeteoMarineProvider {
+    name: String,
+    short_name: String,
+    base_url: String,
+    weather: OpenMeteoProvider,
+    http: HttpClient,
+}
+
+impl Default for OpenMeteoMarineProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenMeteoMarineProvider {
+    pub fn new() -> Self {
+        Self {
+            name: "openmeteo-marine".to_string(),
+            short_name: "omm".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            weather: OpenMeteoProvider::new(),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// API roots of the marine (`/marine`) and the atmospheric
+    /// (`/forecast`) requests
+    pub fn with_base_urls(mut self, marine: &str, weather: &str) -> Self {
+        self.base_url = marine.to_string();
+        self.weather = self.weather.with_base_url(weather);
+        self
+    }
+
+    /// Client used for the marine and the atmospheric requests
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.weather = self.weather.with_http(http.clone());
+        self.http = http;
+        self
+    }
+
+    const HOURLY_PARAMS: [&'static str; 7] = [
+        "swell_wave_height",
+        "swell_wave_period",
+        "swell_wave_direction",
+        "wind_wave_height",
+        "wind_wave_period",
+        "wind_wave_direction",
+        "sea_surface_temperature",
+    ];
+
+    fn meters(value: f64) -> Length {
+        Length::new(value, LengthUnit::Meters)
+    }
+
+    fn transform_hourly(
+        hourly: RawMarineHourlyData,
+        target_tz: Tz,
+    ) -> Result<Vec<WeatherDataPoint>> {
+        let mut data_points = Vec::with_capacity(hourly.time.len());
+        for (i, &ts) in hourly.time.iter().enumerate() {
+            let utc_datetime = DateTime::<Utc>::from_timestamp(ts, 0)
+                .ok_or(anyhow::anyhow!("Invalid timestamp: {}", ts))?;
+            let local = convert_timezone(UtcTimestamp(utc_datetime), target_tz)?;
+
+            data_points.push(WeatherDataPoint {
+                time: local,
+                air_temperature: None,
+                wind: WindDatapoinSection {
+                    wind_speed: None,
+                    wind_direction: None,
+                    gust: None,
+                    shore_wind: None,
+                },
+                // Requested with length_unit=metric; temperatures default to °C
+                waves: WaveDatapointSection {
+                    swell_height: value_at(&hourly.swell_wave_height, i).map(Self::meters),
+                    swell_period: value_at(&hourly.swell_wave_period, i).map(seconds),
+                    swell_direction: value_at(&hourly.swell_wave_direction, i).map(degrees),
+                    wind_wave_height: value_at(&hourly.wind_wave_height, i).map(Self::meters),
+                    wind_wave_period: value_at(&hourly.wind_wave_period, i).map(seconds),
+                    wind_wave_direction: value_at(&hourly.wind_wave_direction, i).map(degrees),
+                },
+                water_temperature: value_at(&hourly.sea_surface_temperature, i)
+                    .map(|v| Temperature::new(v, TemperatureUnit::Celsius)),
+                clouds: CloudDatapointSection {
+                    cloud_cover: None,
+                    low_cloud_cover: None,
+                    medium_cloud_cover: None,
+                    high_cloud_cover: None,
+                },
+                precipitation: None,
+                tide_height: None,
+                is_daylight: None,
+                sailability: None,
+                gear: None,
+            });
+        }
+        Ok(data_points)
+    }
+
+    /// Fill the atmospheric fields of each marine point from the weather
+    /// point with the same UTC timestamp. Hours missing on either side are
+    /// kept as-is.
+    fn merge_atmospheric(marine: &mut [WeatherDataPoint], weather: &[WeatherDataPoint]) {
+        let by_time: HashMap<DateTime<Utc>, &WeatherDataPoint> =
+            weather.iter().map(|p| (p.time.utc(), p)).collect();
+
+        for point in marine.iter_mut() {
+            if let Some(atmospheric) = by_time.get(&point.time.utc()) {
+                point.fill_missing_from(atmospheric);
+            }
+        }
+    }
+
+    async fn fetch_marine_data(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        lat: f64,
+        lng: f64,
+        target_tz: Tz,
+    ) -> Result<Vec<WeatherDataPoint>> {
+        let key = CacheKey { provider: &self.name, model: "marine", lat, lng, start, end };
+        let request = self
+            .http
+            .get(&format!("{}/marine", self.base_url))
+            .query(&[
+                ("latitude", lat.to_string()),
+                ("longitude", lng.to_string()),
+                ("hourly", Self::HOURLY_PARAMS.join(",")),
+                ("start_hour", start.format("%Y-%m-%dT%H:%M").to_string()),
+                ("end_hour", end.format("%Y-%m-%dT%H:%M").to_string()),
+                ("timezone", "GMT".to_string()),
+                ("timeformat", "unixtime".to_string()),
+                ("length_unit", "metric".to_string()),
+            ]);
+        let response = self
+            .http
+            .send(&key, request)
+            .await
+            .map_err(|e| e.with_context("Failed to connect to Open-Meteo Marine API"))?;
+
+        let status = response.status;
+        let body = response.body;
+
+        if !status.is_success() {
+            return Err(OpenMeteoAPIError::from_status_code_and_body(status.as_u16(), &body).into());
+        }
+
+        let data: RawMarineResponse =
+            serde_json::from_str(&body).context("Failed to parse marine API response")?;
+
+        Self::transform_hourly(data.hourly, target_tz)
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for OpenMeteoMarineProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    async fn fetch_weather_data(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        lat: f64,
+        lng: f64,
+        target_tz: Tz,
+    ) -> Result<WeatherData> {
+        // Execute both requests in parallel
+        let (marine, weather) = tokio::join!(
+            self.fetch_marine_data(start, end, lat, lng, target_tz),
+            self.weather.fetch_weather_data(start, end, lat, lng, target_tz)
+        );
+
+        let mut data_points = marine?;
+        let weather = weather.context("Failed to fetch Open-Meteo atmospheric data")?;
+        Self::merge_atmospheric(&mut data_points, &weather.data_points);
+
+        Ok(WeatherData {
+            data_points,
+            alerts: None,
+            tides: Vec::new(),
+        })
+    }
+}
+
+// ============================================================================
+// Provider Registry
+// ============================================================================
+
+// Register provider with central registry
+inventory::submit! {
+    ProviderMetadata {
+        name: "openmeteo-marine",
+        description: "Open-Meteo Marine API: swell, wind waves and sea temperature (no API key required)",
+        api_key_var: None,
+        base_url: Some(DEFAULT_BASE_URL),
+        base_url_var: Some("OPEN_METEO_MARINE_API_URL"),
+        instantiate: |config| {
+            Ok(Box::new(
+                OpenMeteoMarineProvider::new()
+                    .with_base_urls(
+                        config.endpoint("openmeteo-marine").unwrap_or(DEFAULT_BASE_URL),
+                        config.endpoint("openmeteo").unwrap_or(crate::providers::openmeteo::DEFAULT_BASE_URL),
+                    )
+                    .with_http(HttpClient::from_config(config)),
+            ))
+        },
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::{Speed, SpeedUnit};
+    use serde_json::json;
+
+    fn marine_points() -> Vec<WeatherDataPoint> {
+        let raw: RawMarineResponse = serde_json::from_value(json!({
+            "hourly": {
+                "time": [1765108800, 1765112400],
+                "swell_wave_height": [1.2, 1.3],
+                "swell_wave_period": [8.5, null],
+                "swell_wave_direction": [310.0, 315.0],
+                "wind_wave_height": [0.4, 0.5],
+                "sea_surface_temperature": [20.1, 20.2]
+            }
+        }))
+        .unwrap();
+        OpenMeteoMarineProvider::transform_hourly(raw.hourly, chrono_tz::UTC).unwrap()
+    }
+
+    #[test]
+    fn test_transform_hourly_fills_wave_section() {
+        let points = marine_points();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].waves.swell_height, Some(Length::new(1.2, LengthUnit::Meters)));
+        assert_eq!(points[1].waves.swell_period, None);
+        assert_eq!(points[1].waves.wind_wave_height.map(|h| h.value()), Some(0.5));
+        assert_eq!(points[0].waves.wind_wave_period, None);
+        assert_eq!(points[0].water_temperature.map(|t| t.value()), Some(20.1));
+        assert!(points[0].wind.wind_speed.is_none());
+    }
+
+    #[test]
+    fn test_merge_atmospheric_matches_by_utc_timestamp() {
+        let mut marine = marine_points();
+
+        // Same instants, different target timezone, reversed order
+        let tz: Tz = "Asia/Jerusalem".parse().unwrap();
+        let mut weather = marine_points();
+        for point in weather.iter_mut() {
+            point.time = convert_timezone(UtcTimestamp(point.time.utc()), tz).unwrap();
+            point.wind.wind_speed = Some(Speed::new(
+                point.time.utc().timestamp() as f64,
+                SpeedUnit::MetersPerSecond,
+            ));
+            point.waves.swell_height = Some(OpenMeteoMarineProvider::meters(99.0));
+        }
+        weather.reverse();
+
+        OpenMeteoMarineProvider::merge_atmospheric(&mut marine, &weather);
+
+        assert_eq!(marine[0].wind.wind_speed.map(|s| s.value()), Some(1765108800.0));
+        assert_eq!(marine[1].wind.wind_speed.map(|s| s.value()), Some(1765112400.0));
+        assert_eq!(
+            marine[0].waves.swell_height.map(|h| h.value()),
+            Some(1.2),
+            "marine values must win"
+        );
+    }
+}
@@ -1,14 +1,15 @@
 use crate::forecast_provider::{
-    CloudDatapointSection, ForecastProvider, UtcTimestamp, WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection, convert_timezone
+    AlertSeverity, CloudDatapointSection, ForecastProvider, UtcTimestamp, WaveDatapointSection, WeatherAlert, WeatherData, WeatherDataPoint, WindDatapoinSection, convert_timezone
 };
+use crate::http::{CacheKey, HttpClient};
 use crate::provider_registry::ProviderMetadata;
+use crate::units::{degrees, Speed, SpeedUnit, Temperature, TemperatureUnit};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use serde::Deserialize;
 use std::env;
-use std::fmt::Display;
 
 #[derive(Debug, Deserialize)]
 struct RawWeatherResponse {
@@ -25,16 +26,6 @@ struct RawAlert {
     description: String,
 }
 
-impl Display for RawAlert {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Alert[{}]: {}\nFrom: {} To: {}\nDescription: {}",
-            self.sender_name, self.event, self.start, self.end, self.description
-        )
-    }
-}
-
 #[derive(Debug, Deserialize)]
 struct RawHourlyData {
     dt: i64,
@@ -46,15 +37,38 @@ struct RawHourlyData {
     wind_speed: Option<f64>,
 }
 
+/// Overridden with `[endpoints] openweathermap` or `OPEN_WEATHER_MAP_API_URL`
+const DEFAULT_BASE_URL: &str = "https://api.openweathermap.org/data/3.0";
+
 pub struct OpenWeatherMapProvider {
     api_key: String,
+    base_url: String,
     name: String,
     short_name: String,
+    http: HttpClient,
 }
 
 impl OpenWeatherMapProvider {
     pub fn new(api_key: String) -> Self {
-        Self { api_key, name: "openweathermap".to_string(), short_name: "owm".to_string() }
+        Self {
+            api_key,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            name: "openweathermap".to_string(),
+            short_name: "owm".to_string(),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// API root the `/onecall` path is appended to
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Client used for all requests, e.g. one with a response cache
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
     }
 
     fn build_weather_data_point(hour: RawHourlyData, target_tz: Tz) -> Result<WeatherDataPoint> {
@@ -66,11 +80,13 @@ impl OpenWeatherMapProvider {
 
         Ok(WeatherDataPoint {
             time: local,
-            air_temperature: Some(hour.air_temperature),
+            // Requested with units=metric: °C and m/s
+            air_temperature: Some(Temperature::new(hour.air_temperature, TemperatureUnit::Celsius)),
             wind: WindDatapoinSection {
-                wind_speed: hour.wind_speed,
-                wind_direction: hour.wind_deg,
-                gust: hour.wind_gust,
+                wind_speed: hour.wind_speed.map(|v| Speed::new(v, SpeedUnit::MetersPerSecond)),
+                wind_direction: hour.wind_deg.map(degrees),
+                gust: hour.wind_gust.map(|v| Speed::new(v, SpeedUnit::MetersPerSecond)),
+                shore_wind: None,
             },
             waves: WaveDatapointSection {
                 swell_height: None,
@@ -88,6 +104,27 @@ impl OpenWeatherMapProvider {
                 high_cloud_cover: None,
             },
             precipitation: None, // TODO: Map precipitation if available
+            tide_height: None,
+            is_daylight: None,
+            sailability: None,
+            gear: None,
+        })
+    }
+
+    /// `None` when the alert's start or end is not a valid timestamp
+    fn build_alert(alert: RawAlert, target_tz: Tz) -> Option<WeatherAlert> {
+        let local = |ts: i64| {
+            let utc = DateTime::<Utc>::from_timestamp(ts, 0)?;
+            convert_timezone(UtcTimestamp(utc), target_tz).ok()
+        };
+
+        Some(WeatherAlert {
+            sender: alert.sender_name,
+            severity: AlertSeverity::from_event(&alert.event),
+            event: alert.event,
+            start: local(alert.start)?,
+            end: local(alert.end)?,
+            description: alert.description,
         })
     }
 }
@@ -124,9 +161,10 @@ impl ForecastProvider for OpenWeatherMapProvider {
             "Fetching weather data from {} to {} for coordinates ({}, {})",
             start, end, lat, lng
         );
-        let client = reqwest::Client::new();
-        let req = client
-            .get("https://api.openweathermap.org/data/3.0/onecall")
+        let key = CacheKey { provider: &self.name, model: "onecall", lat, lng, start, end };
+        let req = self
+            .http
+            .get(&format!("{}/onecall", self.base_url))
             .query(&[
                 ("lat", &lat.to_string()),
                 ("lon", &lng.to_string()),
@@ -134,16 +172,14 @@ impl ForecastProvider for OpenWeatherMapProvider {
                 ("units", &"metric".to_string()),
                 ("mode", &"json".to_string()),
             ]);
-        let response = req
-            .send()
+        let response = self
+            .http
+            .send(&key, req)
             .await
-            .context("Failed to connect to openweathermap API")?;
+            .map_err(|e| e.with_context("Failed to connect to openweathermap API"))?;
 
-        let status = response.status();
-        let response_text = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
+        let status = response.status;
+        let response_text = response.body;
 
         if !status.is_success() {
             return Err(anyhow::anyhow!(
@@ -157,27 +193,12 @@ impl ForecastProvider for OpenWeatherMapProvider {
         let data: RawWeatherResponse =
             serde_json::from_str(&response_text).context("Failed to parse API response")?;
 
-        let alerts_formatted = data.alerts.map(|alerts| {
+        let alerts = data.alerts.map(|alerts| {
             alerts
                 .into_iter()
-                // filter_map to convert and skip any invalid timestamps
-                .filter_map(|alert| {
-                    let utc_start = DateTime::<Utc>::from_timestamp(alert.start, 0)?;
-                    let utc_end = DateTime::<Utc>::from_timestamp(alert.end, 0)?;
-
-                    let start_local = target_tz.from_utc_datetime(&utc_start.naive_utc());
-                    let end_local = target_tz.from_utc_datetime(&utc_end.naive_utc());
-
-                    Some(format!(
-                        "Alert[{}]: {}\nFrom: {} To: {}\nDescription: {}",
-                        alert.sender_name,
-                        alert.event,
-                        start_local.format("%Y-%m-%d %H:%M"),
-                        end_local.format("%Y-%m-%d %H:%M"),
-                        alert.description
-                    ))
-                })
-                .collect::<Vec<String>>()
+                // skip alerts with invalid timestamps
+                .filter_map(|alert| Self::build_alert(alert, target_tz))
+                .collect::<Vec<WeatherAlert>>()
         });
 
         let mut data_points = Vec::with_capacity(data.hourly.len());
@@ -185,7 +206,7 @@ impl ForecastProvider for OpenWeatherMapProvider {
             data_points.push(Self::build_weather_data_point(hour, target_tz)?);
         }
 
-        Ok(WeatherData{data_points, alerts: alerts_formatted})
+        Ok(WeatherData{data_points, alerts, tides: Vec::new()})
     }
 }
 
@@ -198,10 +219,63 @@ inventory::submit! {
     ProviderMetadata {
         name: "openweathermap",
         description: "OpenWeatherMap Global Weather Data",
-        api_key_var: "OPEN_WEATHER_MAP_API_KEY",
-        instantiate: || {
+        api_key_var: Some("OPEN_WEATHER_MAP_API_KEY"),
+        base_url: Some(DEFAULT_BASE_URL),
+        base_url_var: Some("OPEN_WEATHER_MAP_API_URL"),
+        instantiate: |config| {
             let api_key = OpenWeatherMapProvider::get_api_key()?;
-            Ok(Box::new(OpenWeatherMapProvider::new(api_key)))
+            Ok(Box::new(
+                OpenWeatherMapProvider::new(api_key)
+                    .with_base_url(config.endpoint("openweathermap").unwrap_or(DEFAULT_BASE_URL))
+                    .with_http(HttpClient::from_config(config)),
+            ))
         },
     }
 }
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_alert_keeps_fields_structured() {
+        let raw: RawAlert = serde_json::from_value(json!({
+            "sender_name": "NWS Boston",
+            "event": "Gale Warning",
+            "start": 1765108800,
+            "end": 1765152000,
+            "description": "Northwest winds 25 to 35 kt.",
+            "tags": ["Wind"]
+        }))
+        .unwrap();
+
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let alert = OpenWeatherMapProvider::build_alert(raw, tz).unwrap();
+
+        assert_eq!(alert.sender, "NWS Boston");
+        assert_eq!(alert.event, "Gale Warning");
+        assert_eq!(alert.severity, Some(AlertSeverity::Warning));
+        assert_eq!(alert.start.utc().timestamp(), 1765108800);
+        assert_eq!(alert.end.local().format("%Y-%m-%d %H:%M").to_string(), "2025-12-07 19:00");
+
+        let json = serde_json::to_value(&alert).unwrap();
+        assert_eq!(json["start"], "2025-12-07 07:00");
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["description"], "Northwest winds 25 to 35 kt.");
+    }
+
+    #[test]
+    fn test_build_alert_skips_invalid_timestamps() {
+        let raw = RawAlert {
+            sender_name: "NWS".to_string(),
+            event: "Special Statement".to_string(),
+            start: i64::MAX,
+            end: 0,
+            description: String::new(),
+        };
+        assert!(OpenWeatherMapProvider::build_alert(raw, chrono_tz::UTC).is_none());
+    }
+}
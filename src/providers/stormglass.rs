@@ -2,15 +2,18 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
-use reqwest;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
 use thiserror::Error;
 
 use crate::forecast_provider::{
-    CloudDatapointSection, ForecastProvider, UtcTimestamp, WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection, convert_timezone
+    CloudDatapointSection, ForecastProvider, TideEvent, TideKind, UtcTimestamp, WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection, convert_timezone
 };
+use crate::http::{CacheKey, HttpClient};
 use crate::provider_registry::ProviderMetadata;
+use crate::units::{degrees, seconds, Length, LengthUnit, Speed, SpeedUnit, Temperature, TemperatureUnit};
 
 // ============================================================================
 // Custom Error Types
@@ -89,24 +92,114 @@ struct RawWeatherResponse {
     hours: Vec<RawHourlyData>,
 }
 
+#[derive(Debug, Deserialize)]
+struct RawTideExtreme {
+    time: String,
+    height: f64,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawTideExtremesResponse {
+    data: Vec<RawTideExtreme>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSeaLevel {
+    time: String,
+    sg: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSeaLevelResponse {
+    data: Vec<RawSeaLevel>,
+}
+
 // ============================================================================
 // StormGlass Provider
 // ============================================================================
 
+/// Overridden with `[endpoints] stormglass` or `STORMGLASS_API_URL`
+const DEFAULT_BASE_URL: &str = "https://api.stormglass.io/v2";
+
 pub struct StormGlassProvider {
     api_key: String,
     name: String,
     short_name: String,
-    api_endpoint: String,
+    base_url: String,
+    /// Also fetch tide extremes and hourly sea level (two extra requests)
+    tides: bool,
+    http: HttpClient,
 }
 
 impl StormGlassProvider {
     pub fn new(api_key: String) -> Self {
-        Self { api_key, name: "stormglass".to_string(), short_name: "sg".to_string(), api_endpoint: "https://api.stormglass.io/v2/weather/point".to_string() }
+        Self {
+            api_key,
+            name: "stormglass".to_string(),
+            short_name: "sg".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            tides: false,
+            http: HttpClient::default(),
+        }
     }
 
-    /// Convert m/s to knots
-    const MS_TO_KNOTS: f64 = 1.94384;
+    /// Enable or disable the tide requests
+    pub fn with_tides(mut self, tides: bool) -> Self {
+        self.tides = tides;
+        self
+    }
+
+    /// API root the endpoint paths (`/weather/point`, `/tide/...`) are appended to
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Client used for all requests, e.g. one with a response cache
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// GET `path` under the base URL with the common location and time range parameters
+    async fn get<T: DeserializeOwned>(
+        &self,
+        key: &CacheKey<'_>,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        let request = self
+            .http
+            .get(&format!("{}{}", self.base_url, path))
+            .query(query)
+            .header("Authorization", &self.api_key);
+        let response = self
+            .http
+            .send(key, request)
+            .await
+            .map_err(|e| e.with_context("Failed to connect to Storm Glass API"))?;
+
+        if !response.status.is_success() {
+            return Err(StormGlassAPIError::from_status_code(response.status.as_u16()).into());
+        }
+
+        serde_json::from_str(&response.body).context("Failed to parse API response")
+    }
+
+    /// StormGlass reports wind in m/s; the output units convert it
+    fn wind_speed(source: SourceData) -> Speed {
+        Speed::new(source.sg, SpeedUnit::MetersPerSecond)
+    }
+
+    fn meters(source: SourceData) -> Length {
+        Length::new(source.sg, LengthUnit::Meters)
+    }
+
+    fn celsius(source: SourceData) -> Temperature {
+        Temperature::new(source.sg, TemperatureUnit::Celsius)
+    }
 
     fn transform_hour(hour: RawHourlyData, target_tz: Tz) -> Result<WeatherDataPoint> {
         let utc = UtcTimestamp::from_rfc3339(&hour.time).context("Failed to parse timestamp")?;
@@ -115,21 +208,22 @@ impl StormGlassProvider {
 
         Ok(WeatherDataPoint {
             time: local,
-            air_temperature: hour.air_temperature.map(|s| s.sg),
+            air_temperature: hour.air_temperature.map(Self::celsius),
             wind: WindDatapoinSection {
-                wind_speed: hour.wind_speed.map(|s| s.sg * Self::MS_TO_KNOTS),
-                wind_direction: hour.wind_direction.map(|s| s.sg),
-                gust: hour.gust.map(|s| s.sg * Self::MS_TO_KNOTS),
+                wind_speed: hour.wind_speed.map(Self::wind_speed),
+                wind_direction: hour.wind_direction.map(|s| degrees(s.sg)),
+                gust: hour.gust.map(Self::wind_speed),
+                shore_wind: None,
             },
             waves: WaveDatapointSection {
-                swell_height: hour.swell_height.map(|s| s.sg),
-                swell_period: hour.swell_period.map(|s| s.sg),
-                swell_direction: hour.swell_direction.map(|s| s.sg),
-                wind_wave_height: hour.wind_wave_height.map(|s|s.sg),
-                wind_wave_period: hour.wind_wave_period.map(|s|s.sg),
-                wind_wave_direction: hour.wind_wave_direction.map(|s|s.sg),
+                swell_height: hour.swell_height.map(Self::meters),
+                swell_period: hour.swell_period.map(|s| seconds(s.sg)),
+                swell_direction: hour.swell_direction.map(|s| degrees(s.sg)),
+                wind_wave_height: hour.wind_wave_height.map(Self::meters),
+                wind_wave_period: hour.wind_wave_period.map(|s| seconds(s.sg)),
+                wind_wave_direction: hour.wind_wave_direction.map(|s| degrees(s.sg)),
             },
-            water_temperature: hour.water_temperature.map(|s| s.sg),
+            water_temperature: hour.water_temperature.map(Self::celsius),
             clouds: CloudDatapointSection {
                 cloud_cover: hour.cloud_cover.map(|s| s.sg),
                 low_cloud_cover: None,
@@ -137,8 +231,50 @@ impl StormGlassProvider {
                 high_cloud_cover: None,
             },
             precipitation: hour.precipitation.map(|s| s.sg),
+            // Filled from the sea-level endpoint when tides are requested
+            tide_height: None,
+            is_daylight: None,
+            sailability: None,
+            gear: None,
         })
     }
+
+    fn transform_tide_extremes(raw: RawTideExtremesResponse, target_tz: Tz) -> Result<Vec<TideEvent>> {
+        raw.data
+            .into_iter()
+            .map(|extreme| {
+                let kind = match extreme.kind.as_str() {
+                    "high" => TideKind::High,
+                    "low" => TideKind::Low,
+                    other => anyhow::bail!("Unknown tide extreme type '{}'", other),
+                };
+                let utc = UtcTimestamp::from_rfc3339(&extreme.time)
+                    .context("Failed to parse tide extreme timestamp")?;
+                Ok(TideEvent {
+                    time: convert_timezone(utc, target_tz)?,
+                    height: Length::new(extreme.height, LengthUnit::Meters),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// Attach the hourly sea level to the data points with the same UTC hour
+    fn attach_sea_level(points: &mut [WeatherDataPoint], raw: RawSeaLevelResponse) -> Result<()> {
+        let mut heights = HashMap::with_capacity(raw.data.len());
+        for level in raw.data {
+            let utc = UtcTimestamp::from_rfc3339(&level.time)
+                .context("Failed to parse sea level timestamp")?;
+            heights.insert(utc.0, level.sg);
+        }
+
+        for point in points.iter_mut() {
+            point.tide_height = heights
+                .get(&point.time.utc())
+                .map(|&height| Length::new(height, LengthUnit::Meters));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -184,39 +320,37 @@ impl ForecastProvider for StormGlassProvider {
             start, end, lat, lng
         );
 
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&self.api_endpoint)
-            .query(&[
-                ("lat", lat.to_string()),
-                ("lng", lng.to_string()),
-                ("params", params.join(",")),
-                ("start", start.timestamp().to_string()),
-                ("end", end.timestamp().to_string()),
-                ("source", "sg".to_string()),
-            ])
-            .header("Authorization", &self.api_key)
-            .send()
-            .await
-            .context("Failed to connect to Storm Glass API")?;
-
-        let status = response.status();
-
-        if !status.is_success() {
-            return Err(StormGlassAPIError::from_status_code(status.as_u16()).into());
-        }
+        let key = |model| CacheKey { provider: &self.name, model, lat, lng, start, end };
+        let range = [
+            ("lat", lat.to_string()),
+            ("lng", lng.to_string()),
+            ("start", start.timestamp().to_string()),
+            ("end", end.timestamp().to_string()),
+        ];
+        let mut query = range.to_vec();
+        query.push(("params", params.join(",")));
+        query.push(("source", "sg".to_string()));
 
-        let data = response
-            .json::<RawWeatherResponse>()
-            .await
-            .context("Failed to parse API response")?;
+        let data: RawWeatherResponse = self.get(&key("weather"), "/weather/point", &query).await?;
 
         let mut data_points = Vec::with_capacity(data.hours.len());
         for hour in data.hours {
             data_points.push(Self::transform_hour(hour, target_tz)?);
         }
 
-        Ok(WeatherData { data_points, alerts: None })
+        let mut tides = Vec::new();
+        if self.tides {
+            let (extremes_key, sea_level_key) = (key("tide-extremes"), key("sea-level"));
+            let (extremes, sea_level) = futures::try_join!(
+                self.get::<RawTideExtremesResponse>(&extremes_key, "/tide/extremes/point", &range),
+                self.get::<RawSeaLevelResponse>(&sea_level_key, "/tide/sea-level/point", &range),
+            )
+            .context("Failed to fetch tide data")?;
+            tides = Self::transform_tide_extremes(extremes, target_tz)?;
+            Self::attach_sea_level(&mut data_points, sea_level)?;
+        }
+
+        Ok(WeatherData { data_points, alerts: None, tides })
     }
 }
 
@@ -229,10 +363,85 @@ inventory::submit! {
     ProviderMetadata {
         name: "stormglass",
         description: "StormGlass Marine Weather API",
-        api_key_var: "STORMGLASS_API_KEY",
-        instantiate: || {
+        api_key_var: Some("STORMGLASS_API_KEY"),
+        base_url: Some(DEFAULT_BASE_URL),
+        base_url_var: Some("STORMGLASS_API_URL"),
+        instantiate: |config| {
             let api_key = StormGlassProvider::get_api_key()?;
-            Ok(Box::new(StormGlassProvider::new(api_key)))
+            Ok(Box::new(
+                StormGlassProvider::new(api_key)
+                    .with_tides(config.tides)
+                    .with_base_url(config.endpoint("stormglass").unwrap_or(DEFAULT_BASE_URL))
+                    .with_http(HttpClient::from_config(config)),
+            ))
         },
     }
 }
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hour(time: &str) -> WeatherDataPoint {
+        let utc = UtcTimestamp::from_rfc3339(time).unwrap();
+        WeatherDataPoint::empty(convert_timezone(utc, chrono_tz::UTC).unwrap())
+    }
+
+    #[test]
+    fn test_transform_tide_extremes() {
+        let raw: RawTideExtremesResponse = serde_json::from_value(json!({
+            "data": [
+                {"height": 0.32, "time": "2025-12-08T02:34:00+00:00", "type": "high"},
+                {"height": -0.28, "time": "2025-12-08T08:51:00+00:00", "type": "low"}
+            ],
+            "meta": {"datum": "MSL"}
+        }))
+        .unwrap();
+
+        let tz: Tz = "Asia/Jerusalem".parse().unwrap();
+        let tides = StormGlassProvider::transform_tide_extremes(raw, tz).unwrap();
+
+        assert_eq!(tides.len(), 2);
+        assert_eq!(tides[0].kind, TideKind::High);
+        assert_eq!(tides[0].height, Length::new(0.32, LengthUnit::Meters));
+        assert_eq!(tides[0].time.local().format("%H:%M").to_string(), "04:34");
+        assert_eq!(tides[1].kind, TideKind::Low);
+    }
+
+    #[test]
+    fn test_transform_tide_extremes_rejects_unknown_type() {
+        let raw: RawTideExtremesResponse = serde_json::from_value(json!({
+            "data": [{"height": 0.1, "time": "2025-12-08T02:34:00+00:00", "type": "slack"}]
+        }))
+        .unwrap();
+
+        assert!(StormGlassProvider::transform_tide_extremes(raw, chrono_tz::UTC).is_err());
+    }
+
+    #[test]
+    fn test_attach_sea_level_matches_by_utc_hour() {
+        let raw: RawSeaLevelResponse = serde_json::from_value(json!({
+            "data": [
+                {"sg": 0.21, "time": "2025-12-08T00:00:00+00:00"},
+                {"sg": 0.15, "time": "2025-12-08T01:00:00+00:00"}
+            ]
+        }))
+        .unwrap();
+
+        let mut points = vec![hour("2025-12-08T01:00:00Z"), hour("2025-12-08T02:00:00Z")];
+        StormGlassProvider::attach_sea_level(&mut points, raw).unwrap();
+
+        assert_eq!(points[0].tide_height, Some(Length::new(0.15, LengthUnit::Meters)));
+        assert_eq!(points[1].tide_height, None, "no sea level for this hour");
+    }
+
+    #[test]
+    fn test_tides_are_off_by_default() {
+        let provider = StormGlassProvider::new("key".to_string());
+        assert!(!provider.tides);
+        assert!(provider.with_tides(true).tides);
+    }
+}
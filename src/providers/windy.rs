@@ -2,7 +2,6 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
-use reqwest;
 use serde::{Deserialize, Serialize};
 use std::env;
 use thiserror::Error;
@@ -11,7 +10,11 @@ use crate::forecast_provider::{
     convert_timezone, CloudDatapointSection, ForecastProvider, LocalTimestamp, UtcTimestamp,
     WaveDatapointSection, WeatherData, WeatherDataPoint, WindDatapoinSection,
 };
+use crate::http::{CacheKey, HttpClient};
 use crate::provider_registry::ProviderMetadata;
+use crate::units::{
+    degrees, seconds, Length, LengthUnit, Speed, SpeedUnit, Temperature, TemperatureUnit,
+};
 
 // ============================================================================
 // Custom Error Types
@@ -47,11 +50,6 @@ impl WindyAPIError {
 // Windy-Specific Data Structures
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
-struct SourceData {
-    sg: f64,
-}
-
 #[derive(Debug, Deserialize)]
 enum Unit {
     #[serde(rename = "m")]
@@ -66,46 +64,55 @@ enum Unit {
     MetersPerSecond,
     #[serde(rename = "%")]
     Percentage,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Deserialize)]
-struct GfsUnitsRsponse {
-    #[serde(rename = "waves_height-surface")]
-    waves_hight: Option<Unit>,
-    #[serde(rename = "waves_period-surface")]
-    waves_period: Option<Unit>,
-    #[serde(rename = "waves_direction-surface")]
-    waves_direction: Option<Unit>,
-    #[serde(rename = "wwaves_height-surface")]
-    wind_waves_hight: Option<Unit>,
-    #[serde(rename = "wwaves_period-surface")]
-    wind_waves_period: Option<Unit>,
-    #[serde(rename = "wwaves_direction-surface")]
-    wind_waves_direction: Option<Unit>,
-    #[serde(rename = "swell1_direction-surface")]
-    swell1_direction: Option<Unit>,
-    #[serde(rename = "swell1_height-surface")]
-    swell1_hight: Option<Unit>,
-    #[serde(rename = "swell1_period-surface")]
-    swell1_period: Option<Unit>,
+/// Typed units for the values Windy reports. A missing unit (Windy omits
+/// units of parameters it has no data for) falls back to the documented default.
+impl Unit {
+    fn speed(unit: &Option<Unit>) -> Result<SpeedUnit> {
+        match unit {
+            None | Some(Unit::MetersPerSecond) => Ok(SpeedUnit::MetersPerSecond),
+            Some(other) => anyhow::bail!("Unexpected Windy speed unit: {:?}", other),
+        }
+    }
+
+    fn length(unit: &Option<Unit>) -> Result<LengthUnit> {
+        match unit {
+            None | Some(Unit::Meters) => Ok(LengthUnit::Meters),
+            Some(other) => anyhow::bail!("Unexpected Windy length unit: {:?}", other),
+        }
+    }
+
+    fn temperature(unit: &Option<Unit>) -> Result<TemperatureUnit> {
+        match unit {
+            None | Some(Unit::Kelvins) => Ok(TemperatureUnit::Kelvin),
+            Some(other) => anyhow::bail!("Unexpected Windy temperature unit: {:?}", other),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
-struct GfsWaveUnitsRsponse {
+struct GfsUnitsRsponse {
     #[serde(rename = "wind_u-surface")]
     wind_west: Option<Unit>,
-    #[serde(rename = "wind_v-surface")]
-    wind_south: Option<Unit>,
     #[serde(rename = "gust-surface")]
     gust: Option<Unit>,
     #[serde(rename = "temp-surface")]
     temprature: Option<Unit>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GfsWaveUnitsRsponse {
+    #[serde(rename = "wwaves_height-surface")]
+    wind_waves_hight: Option<Unit>,
+    #[serde(rename = "swell1_height-surface")]
+    swell1_hight: Option<Unit>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GfsRawWeatherResponse {
-    #[serde(rename = "ts")]
-    local_epoch_ts: Vec<i64>,
     units: GfsUnitsRsponse,
     #[serde(rename = "temp-surface")]
     air_temperature: Option<Vec<f64>>,
@@ -148,16 +155,38 @@ struct GfsWaveRawWeatherResponse {
 // Windy Provider
 // ============================================================================
 
+/// Overridden with `[endpoints] windy` or `WINDY_API_URL`
+const DEFAULT_BASE_URL: &str = "https://api.windy.com/api";
+
 pub struct WindyProvider {
     api_key: String,
-    api_url: String,
+    base_url: String,
     name: String,
     short_name: String,
+    http: HttpClient,
 }
 
 impl WindyProvider {
-    pub fn new(api_key: String, api_url: String) -> Self {
-        Self { api_key, api_url, name: "windy".to_string(), short_name: "windy".to_string()}
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            name: "windy".to_string(),
+            short_name: "windy".to_string(),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// API root the `/point-forecast/v2` path is appended to
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Client used for all requests, e.g. one with a response cache
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
     }
 }
 
@@ -227,26 +256,22 @@ impl ForecastProvider for WindyProvider {
             start, end, lat, lng
         );
 
-        let client = reqwest::Client::new();
+        let gfs_wave_key = CacheKey { provider: &self.name, model: "gfsWave", lat, lng, start, end };
+        let gfs_key = CacheKey { model: "gfs", ..gfs_wave_key };
+        let url = format!("{}/point-forecast/v2", self.base_url);
         // Execute both requests in parallel
         let (gfs_wave_response, gfs_response) = tokio::join!(
-            client
-                .post(self.api_url.as_str())
-                .json(&gfs_wave_body)
-                .send(),
-            client.post(self.api_url.as_str()).json(&gfs_body).send()
+            self.http.send(&gfs_wave_key, self.http.post(&url).json(&gfs_wave_body)),
+            self.http.send(&gfs_key, self.http.post(&url).json(&gfs_body))
         );
 
         // Handle first response
         let gfs_wave_response =
-            gfs_wave_response.context("Failed to connect to Windy API (gfsWave)")?;
+            gfs_wave_response.map_err(|e| e.with_context("Failed to connect to Windy API (gfsWave)"))?;
 
-        let gfs_wave_status = gfs_wave_response.status();
+        let gfs_wave_status = gfs_wave_response.status;
 
-        let gfs_wave_body = gfs_wave_response
-            .text()
-            .await
-            .context("Failed to read gfsWave response body")?;
+        let gfs_wave_body = gfs_wave_response.body;
 
         if !gfs_wave_status.is_success() || gfs_wave_status == reqwest::StatusCode::NO_CONTENT {
             return Err(WindyAPIError::from_status_code_and_body(
@@ -257,14 +282,12 @@ impl ForecastProvider for WindyProvider {
         }
 
         // Handle second response
-        let gfs_response = gfs_response.context("Failed to connect to Windy API (gfs)")?;
+        let gfs_response =
+            gfs_response.map_err(|e| e.with_context("Failed to connect to Windy API (gfs)"))?;
 
-        let gfs_status = gfs_response.status();
+        let gfs_status = gfs_response.status;
 
-        let gfs_body = gfs_response
-            .text()
-            .await
-            .context("Failed to read gfsWave response body")?;
+        let gfs_body = gfs_response.body;
 
         if !gfs_status.is_success() || gfs_status == reqwest::StatusCode::NO_CONTENT {
             return Err(
@@ -287,6 +310,7 @@ impl ForecastProvider for WindyProvider {
         Ok(WeatherData {
             data_points,
             alerts: None,
+            tides: Vec::new(),
         })
     }
 }
@@ -301,33 +325,45 @@ impl WindyProvider {
         // Assuming both responses have the same length and timestamps
         // we iterate over the two responses simultaneously, in each response we iterate over
         // all the fields simultaneously. All the fields are vectors of the same length.
+        let wind_unit = Unit::speed(&gfs_data.units.wind_west)?;
+        let gust_unit = Unit::speed(&gfs_data.units.gust)?;
+        let temperature_unit = Unit::temperature(&gfs_data.units.temprature)?;
+        let swell_unit = Unit::length(&gfs_wave_data.units.swell1_hight)?;
+        let wind_wave_unit = Unit::length(&gfs_wave_data.units.wind_waves_hight)?;
+
         for i in 0..gfs_wave_data.local_epoch_ts.len() {
             let data_point: WeatherDataPoint = WeatherDataPoint {
                 time: Self::convert_timestamp(gfs_wave_data.local_epoch_ts[i], target_tz)?,
-                air_temperature: gfs_data
-                    .air_temperature
-                    .as_ref()
-                    .map(|v| Self::kelvin_to_celcius(v[i])),
+                air_temperature: gfs_data.air_temperature.as_ref().map(|v| {
+                    Temperature::new(v[i], temperature_unit).to(TemperatureUnit::Celsius)
+                }),
                 wind: WindDatapoinSection {
                     wind_speed: gfs_data
                         .wind_west
                         .as_ref()
                         .zip(gfs_data.wind_south.as_ref())
-                        .map(|(west, south)| Self::calc_wind_speed(west[i], south[i])),
+                        .map(|(west, south)| {
+                            Speed::new(Self::calc_wind_speed(west[i], south[i]), wind_unit)
+                        }),
                     wind_direction: gfs_data
                         .wind_west
                         .as_ref()
                         .zip(gfs_data.wind_south.as_ref())
-                        .map(|(west, south)| Self::calc_wind_direction(west[i], south[i])),
-                    gust: gfs_data.gust.as_ref().map(|v| v[i]),
+                        .map(|(west, south)| degrees(Self::calc_wind_direction(west[i], south[i]))),
+                    gust: gfs_data.gust.as_ref().map(|v| Speed::new(v[i], gust_unit)),
+                    shore_wind: None,
                 },
                 waves: WaveDatapointSection {
-                    swell_height: gfs_wave_data.swell1_hight.as_ref().map(|v| v[i]),
-                    swell_period: gfs_wave_data.swell1_period.as_ref().map(|v| v[i]),
-                    swell_direction: gfs_wave_data.swell1_direction.as_ref().map(|v| v[i]),
-                    wind_wave_height: gfs_wave_data.wind_waves_hight.as_ref().and_then(|v| v[i]),
-                    wind_wave_period: gfs_wave_data.wind_waves_period.as_ref().and_then(|v| v[i]),
-                    wind_wave_direction: gfs_wave_data.wind_waves_direction.as_ref().and_then(|v| v[i]),
+                    swell_height: gfs_wave_data.swell1_hight.as_ref().map(|v| Length::new(v[i], swell_unit)),
+                    swell_period: gfs_wave_data.swell1_period.as_ref().map(|v| seconds(v[i])),
+                    swell_direction: gfs_wave_data.swell1_direction.as_ref().map(|v| degrees(v[i])),
+                    wind_wave_height: gfs_wave_data
+                        .wind_waves_hight
+                        .as_ref()
+                        .and_then(|v| v[i])
+                        .map(|h| Length::new(h, wind_wave_unit)),
+                    wind_wave_period: gfs_wave_data.wind_waves_period.as_ref().and_then(|v| v[i]).map(seconds),
+                    wind_wave_direction: gfs_wave_data.wind_waves_direction.as_ref().and_then(|v| v[i]).map(degrees),
                 },
 
                 water_temperature: None,
@@ -339,6 +375,11 @@ impl WindyProvider {
                 },
 
                 precipitation: gfs_data.precipitation.as_ref().map(|v| v[i]),
+
+                tide_height: None,
+                is_daylight: None,
+                sailability: None,
+                gear: None,
             };
             weather_points.push(data_point);
         }
@@ -361,10 +402,6 @@ impl WindyProvider {
         // north = 0, east = 90, south = 180, west = 270
         (270.0 - angle_deg) % 360.0
     }
-
-    fn kelvin_to_celcius(kelvin: f64) -> f64 {
-        kelvin - 273.15
-    }
 }
 
 // ============================================================================
@@ -376,13 +413,15 @@ inventory::submit! {
     ProviderMetadata {
         name: "windy",
         description: "Windy.com Weather API",
-        api_key_var: "WINDY_API_KEY",
-        instantiate: || {
+        api_key_var: Some("WINDY_API_KEY"),
+        base_url: Some(DEFAULT_BASE_URL),
+        base_url_var: Some("WINDY_API_URL"),
+        instantiate: |config| {
             let api_key = WindyProvider::get_api_key()?;
             Ok(Box::new(
-                WindyProvider::new(
-                    api_key,
-                    "https://api.windy.com/api/point-forecast/v2".to_string())))
+                WindyProvider::new(api_key)
+                    .with_base_url(config.endpoint("windy").unwrap_or(DEFAULT_BASE_URL))
+                    .with_http(HttpClient::from_config(config))))
         },
     }
 }
@@ -0,0 +1,36 @@
+//! Forecast report written for a single location
+//!
+//! [`TransformedWeatherResponse`] is what every `--format` renders: the
+//! prepared hours, the session windows found in them, tides and alerts, and
+//! the metadata describing location, window, provider and units.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::forecast_provider::{TideEvent, WeatherAlert, WeatherDataPoint};
+use crate::sessions::SessionWindow;
+use crate::sun::SunDay;
+
+#[derive(Debug, Serialize)]
+pub struct TransformedMetaData {
+    pub lat: f64,
+    pub lng: f64,
+    pub start: String,
+    pub end: String,
+    #[serde(rename = "report_generated_at")]
+    pub report_generated_at: String,
+    pub provider: String,
+    pub units: HashMap<String, String>,
+    pub sun: Vec<SunDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransformedWeatherResponse {
+    pub hours: Vec<WeatherDataPoint>,
+    pub sessions: Vec<SessionWindow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tides: Vec<TideEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<WeatherAlert>,
+    pub meta: TransformedMetaData,
+}
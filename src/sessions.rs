@@ -0,0 +1,300 @@
+//! Sailability scoring and session windows
+//!
+//! Every hour is checked against the `[rider]` thresholds. Hours that pass
+//! all of them are sailable and get a score from 50 to 100: half of the
+//! bonus rewards wind in the middle of the rider's range, the other half
+//! steady wind (a low gust factor). Hours that fail any check score 0 and
+//! list the reasons as issues.
+//!
+//! Consecutive sailable hours are merged into session windows. Points count
+//! as consecutive when they are at most one time step apart (the smallest
+//! gap in the data, e.g. one hour for hourly and three for 3-hourly data),
+//! so a missing hour splits a window. A window's end is one time step after
+//! its last sailable point, and its gear is the gear recommended for its
+//! peak hour.
+//!
+//! Night hours are recognised by the data point's `is_daylight` flag; hours
+//! without the flag are not treated as night.
+//!
+//! Wind thresholds are in knots; scoring converts each hour's values before
+//! comparing, so it must run before the values are rounded by a conversion
+//! to Beaufort.
+
+use chrono::Duration;
+use serde::Serialize;
+
+use crate::config::loader::RiderConfig;
+use crate::forecast_provider::{LocalTimestamp, WeatherDataPoint};
+use crate::gear::GearRecommendation;
+use crate::units::{Speed, SpeedUnit};
+
+/// Rating of a single hour
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HourScore {
+    /// 0 when any threshold is violated, otherwise 50-100
+    pub score: u8,
+
+    /// Violated thresholds, e.g. "too-light" or "direction"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<&'static str>,
+}
+
+impl HourScore {
+    pub fn is_sailable(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Rate `point` against the rider thresholds
+pub fn score_hour(point: &WeatherDataPoint, rider: &RiderConfig) -> HourScore {
+    let knots = |q: Speed| q.to(SpeedUnit::Knots).value();
+    let wind = point.wind.wind_speed.map(knots);
+    let gust_factor = wind
+        .zip(point.wind.gust.map(knots))
+        .filter(|(wind, _)| *wind > 0.0)
+        .map(|(wind, gust)| gust / wind);
+
+    let mut issues = Vec::new();
+    match wind {
+        None => issues.push("no-wind-data"),
+        Some(w) if w < rider.min_wind => issues.push("too-light"),
+        Some(w) if w > rider.max_wind => issues.push("too-strong"),
+        Some(_) => {}
+    }
+    if gust_factor.is_some_and(|factor| factor > rider.max_gust_factor) {
+        issues.push("too-gusty");
+    }
+    if let Some(shore) = point.wind.shore_wind {
+        if !rider.allowed_directions.is_empty()
+            && !rider.allowed_directions.iter().any(|d| d.eq_ignore_ascii_case(shore.name()))
+        {
+            issues.push("direction");
+        }
+    }
+    if rider.daylight_only && point.is_daylight == Some(false) {
+        issues.push("night");
+    }
+
+    let score = match wind {
+        Some(wind) if issues.is_empty() => {
+            let position = (wind - rider.min_wind) / (rider.max_wind - rider.min_wind);
+            let strength = 1.0 - (2.0 * position - 1.0).abs();
+            let steadiness = gust_factor.map_or(1.0, |factor| {
+                1.0 - ((factor - 1.0) / (rider.max_gust_factor - 1.0)).clamp(0.0, 1.0)
+            });
+            (50.0 + 25.0 * strength.clamp(0.0, 1.0) + 25.0 * steadiness).round() as u8
+        }
+        _ => 0,
+    };
+
+    HourScore { score, issues }
+}
+
+/// Strongest hour of a session window
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionPeak {
+    pub time: LocalTimestamp,
+
+    #[serde(rename = "windSpeed")]
+    pub wind_speed: Speed,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gust: Option<Speed>,
+}
+
+/// A run of consecutive sailable hours
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionWindow {
+    pub start: LocalTimestamp,
+
+    /// One time step after the last sailable point
+    pub end: LocalTimestamp,
+
+    /// Length of the window in hours
+    pub hours: usize,
+
+    /// Mean score of the window's hours
+    pub score: u8,
+
+    pub peak: SessionPeak,
+
+    /// Gear sized for the peak hour
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gear: Option<GearRecommendation>,
+}
+
+/// Merge consecutive sailable hours of already scored points into windows
+pub fn find_session_windows(points: &[WeatherDataPoint]) -> Vec<SessionWindow> {
+    let sailable = |p: &WeatherDataPoint| p.sailability.as_ref().is_some_and(HourScore::is_sailable);
+    let step = time_step(points);
+
+    points
+        .chunk_by(|a, b| sailable(a) && sailable(b) && b.time.utc() - a.time.utc() <= step)
+        .filter(|run| sailable(&run[0]))
+        .map(|run| session_window(run, step))
+        .collect()
+}
+
+/// Smallest gap between consecutive points, one hour if there is none
+fn time_step(points: &[WeatherDataPoint]) -> Duration {
+    points
+        .windows(2)
+        .map(|pair| pair[1].time.utc() - pair[0].time.utc())
+        .filter(|gap| *gap > Duration::zero())
+        .min()
+        .unwrap_or(Duration::hours(1))
+}
+
+fn session_window(run: &[WeatherDataPoint], step: Duration) -> SessionWindow {
+    let first = &run[0];
+    let last = &run[run.len() - 1];
+    let peak = run
+        .iter()
+        .filter(|p| p.wind.wind_speed.is_some())
+        .max_by(|a, b| {
+            let wind = |p: &WeatherDataPoint| p.wind.wind_speed.map_or(0.0, |q| q.value());
+            wind(a).total_cmp(&wind(b))
+        })
+        .unwrap_or(first);
+    let total: u32 = run
+        .iter()
+        .filter_map(|p| p.sailability.as_ref())
+        .map(|s| s.score as u32)
+        .sum();
+
+    let end = last.time.local() + step;
+
+    SessionWindow {
+        start: first.time.clone(),
+        end: LocalTimestamp::new(end),
+        hours: (end - first.time.local()).num_hours() as usize,
+        score: (total as f64 / run.len() as f64).round() as u8,
+        peak: SessionPeak {
+            time: peak.time.clone(),
+            // Sailable hours always have a wind speed
+            wind_speed: peak.wind.wind_speed.unwrap_or(Speed::new(0.0, SpeedUnit::Knots)),
+            gust: peak.wind.gust,
+        },
+        gear: peak.gear.clone(),
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast_provider::{convert_timezone, UtcTimestamp};
+    use crate::shore::ShoreWind;
+    use chrono::{DateTime, Timelike, Utc};
+
+    /// Hour `hour` of 2024-01-01 UTC with the given wind and gust in knots,
+    /// daylight from 06:00 to 20:00
+    fn point(hour: i64, wind: Option<f64>, gust: Option<f64>) -> WeatherDataPoint {
+        let utc = UtcTimestamp(DateTime::<Utc>::from_timestamp(1_704_067_200 + hour * 3600, 0).unwrap());
+        let mut point = WeatherDataPoint::empty(convert_timezone(utc, chrono_tz::UTC).unwrap());
+        point.wind.wind_speed = wind.map(|w| Speed::new(w, SpeedUnit::Knots));
+        point.wind.gust = gust.map(|g| Speed::new(g, SpeedUnit::Knots));
+        point.is_daylight = Some((6..20).contains(&hour));
+        point
+    }
+
+    #[test]
+    fn test_score_hour_reports_each_violation() {
+        let rider = RiderConfig::default();
+
+        assert_eq!(score_hour(&point(12, Some(8.0), None), &rider).issues, vec!["too-light"]);
+        assert_eq!(score_hour(&point(12, Some(40.0), None), &rider).issues, vec!["too-strong"]);
+        assert_eq!(
+            score_hour(&point(12, Some(15.0), Some(30.0)), &rider).issues,
+            vec!["too-gusty"]
+        );
+        assert_eq!(score_hour(&point(12, None, None), &rider).issues, vec!["no-wind-data"]);
+        assert_eq!(score_hour(&point(2, Some(20.0), None), &rider).issues, vec!["night"]);
+
+        let mut offshore = point(12, Some(20.0), None);
+        offshore.wind.shore_wind = Some(ShoreWind::Offshore);
+        let rating = score_hour(&offshore, &rider);
+        assert_eq!(rating.issues, vec!["direction"]);
+        assert_eq!(rating.score, 0);
+    }
+
+    #[test]
+    fn test_score_hour_prefers_mid_range_steady_wind() {
+        let rider = RiderConfig {
+            min_wind: 10.0,
+            max_wind: 30.0,
+            max_gust_factor: 1.5,
+            ..RiderConfig::default()
+        };
+
+        let ideal = score_hour(&point(12, Some(20.0), Some(20.0)), &rider);
+        assert!(ideal.is_sailable());
+        assert_eq!(ideal.score, 100);
+
+        let edge = score_hour(&point(12, Some(10.0), Some(15.0)), &rider);
+        assert!(edge.is_sailable());
+        assert_eq!(edge.score, 50);
+
+        let night = RiderConfig { daylight_only: false, ..rider };
+        assert!(score_hour(&point(2, Some(20.0), None), &night).is_sailable());
+    }
+
+    #[test]
+    fn test_find_session_windows_merges_consecutive_sailable_hours() {
+        let rider = RiderConfig::default();
+        let mut points: Vec<WeatherDataPoint> = [
+            (9, Some(8.0)),
+            (10, Some(14.0)),
+            (11, Some(20.0)),
+            (12, Some(16.0)),
+            (13, Some(5.0)),
+            (14, Some(18.0)),
+        ]
+        .iter()
+        .map(|&(hour, wind)| point(hour, wind, None))
+        .collect();
+        for p in points.iter_mut() {
+            p.sailability = Some(score_hour(p, &rider));
+        }
+
+        let windows = find_session_windows(&points);
+        assert_eq!(windows.len(), 2);
+
+        let first = &windows[0];
+        assert_eq!(first.hours, 3);
+        assert_eq!(first.start.local().hour(), 10);
+        assert_eq!(first.end.local().hour(), 13);
+        assert_eq!(first.peak.time.local().hour(), 11);
+        assert_eq!(first.peak.wind_speed.value(), 20.0);
+
+        assert_eq!(windows[1].hours, 1);
+        assert_eq!(windows[1].end.local().hour(), 15);
+    }
+
+    #[test]
+    fn test_find_session_windows_splits_at_gaps() {
+        let rider = RiderConfig::default();
+        let score = |mut points: Vec<WeatherDataPoint>| {
+            for p in points.iter_mut() {
+                p.sailability = Some(score_hour(p, &rider));
+            }
+            points
+        };
+
+        // 12:00 is missing from otherwise hourly sailable data
+        let hourly = score([10, 11, 13, 14].iter().map(|&h| point(h, Some(18.0), None)).collect());
+        let windows = find_session_windows(&hourly);
+        assert_eq!(windows.len(), 2);
+        assert_eq!((windows[0].hours, windows[0].end.local().hour()), (2, 12));
+        assert_eq!((windows[1].start.local().hour(), windows[1].hours), (13, 2));
+
+        // 3-hourly data merges across its own step, and breaks at a 6 h gap
+        let three_hourly = score([6, 9, 12, 18].iter().map(|&h| point(h, Some(18.0), None)).collect());
+        let windows = find_session_windows(&three_hourly);
+        assert_eq!(windows.len(), 2);
+        assert_eq!((windows[0].start.local().hour(), windows[0].hours), (6, 9));
+        assert_eq!(windows[0].end.local().hour(), 15);
+        assert_eq!((windows[1].start.local().hour(), windows[1].hours), (18, 3));
+    }
+}
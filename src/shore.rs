@@ -0,0 +1,125 @@
+//! Wind direction relative to the shoreline
+//!
+//! A spot's shore bearing is the compass direction the beach faces, i.e. the
+//! bearing from the waterline straight out to sea. Wind directions are "from"
+//! bearings, so wind blowing from the shore bearing comes off the sea
+//! (onshore) and wind from the opposite bearing blows out to sea (offshore).
+//!
+//! The angle between the two is split into five 45° sectors centred on
+//! 0° (onshore), 45°, 90° (cross), 135° and 180° (offshore).
+
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Wind direction classified against the shoreline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShoreWind {
+    Onshore,
+    CrossOnshore,
+    Cross,
+    CrossOffshore,
+    /// Blows away from the beach; drifting riders are carried out to sea
+    Offshore,
+}
+
+impl ShoreWind {
+    /// Classify wind coming from `wind_from` (degrees) at a shore facing
+    /// `shore_bearing` (degrees)
+    pub fn classify(wind_from: f64, shore_bearing: f64) -> Self {
+        let diff = (wind_from - shore_bearing).rem_euclid(360.0);
+        let angle = diff.min(360.0 - diff);
+
+        match angle {
+            a if a < 22.5 => ShoreWind::Onshore,
+            a if a < 67.5 => ShoreWind::CrossOnshore,
+            a if a <= 112.5 => ShoreWind::Cross,
+            a if a <= 157.5 => ShoreWind::CrossOffshore,
+            _ => ShoreWind::Offshore,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShoreWind::Onshore => "onshore",
+            ShoreWind::CrossOnshore => "cross-onshore",
+            ShoreWind::Cross => "cross",
+            ShoreWind::CrossOffshore => "cross-offshore",
+            ShoreWind::Offshore => "offshore",
+        }
+    }
+}
+
+impl FromStr for ShoreWind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "onshore" => Ok(ShoreWind::Onshore),
+            "cross-onshore" => Ok(ShoreWind::CrossOnshore),
+            "cross" => Ok(ShoreWind::Cross),
+            "cross-offshore" => Ok(ShoreWind::CrossOffshore),
+            "offshore" => Ok(ShoreWind::Offshore),
+            _ => anyhow::bail!(
+                "Unknown wind direction '{}'. Valid directions: onshore, cross-onshore, cross, \
+                 cross-offshore, offshore",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ShoreWind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_sectors() {
+        // West-facing beach (e.g. the Israeli coast)
+        let shore = 270.0;
+        assert_eq!(ShoreWind::classify(270.0, shore), ShoreWind::Onshore);
+        assert_eq!(ShoreWind::classify(300.0, shore), ShoreWind::CrossOnshore);
+        assert_eq!(ShoreWind::classify(225.0, shore), ShoreWind::CrossOnshore);
+        assert_eq!(ShoreWind::classify(0.0, shore), ShoreWind::Cross);
+        assert_eq!(ShoreWind::classify(180.0, shore), ShoreWind::Cross);
+        assert_eq!(ShoreWind::classify(45.0, shore), ShoreWind::CrossOffshore);
+        assert_eq!(ShoreWind::classify(90.0, shore), ShoreWind::Offshore);
+    }
+
+    #[test]
+    fn test_classify_wraps_around_north() {
+        assert_eq!(ShoreWind::classify(350.0, 10.0), ShoreWind::Onshore);
+        assert_eq!(ShoreWind::classify(10.0, 350.0), ShoreWind::Onshore);
+        assert_eq!(ShoreWind::classify(190.0, 10.0), ShoreWind::Offshore);
+    }
+
+    #[test]
+    fn test_serializes_as_kebab_case() {
+        let json = serde_json::to_string(&ShoreWind::CrossOffshore).unwrap();
+        assert_eq!(json, "\"cross-offshore\"");
+        assert_eq!(ShoreWind::CrossOffshore.to_string(), "cross-offshore");
+    }
+
+    #[test]
+    fn test_parse_round_trips_names() {
+        for wind in [
+            ShoreWind::Onshore,
+            ShoreWind::CrossOnshore,
+            ShoreWind::Cross,
+            ShoreWind::CrossOffshore,
+            ShoreWind::Offshore,
+        ] {
+            assert_eq!(wind.name().parse::<ShoreWind>().unwrap(), wind);
+        }
+        assert!("sideshore".parse::<ShoreWind>().is_err());
+    }
+}
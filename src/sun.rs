@@ -0,0 +1,202 @@
+//! Offline sunrise, sunset and civil twilight
+//!
+//! Uses the sunrise equation with the NOAA approximations for the solar
+//! mean anomaly, equation of center and declination, accurate to a minute or
+//! two at the latitudes people sail at. Sunrise and sunset are the moments the
+//! sun's upper limb touches the horizon (−0.833° including refraction); civil
+//! dawn and dusk are when its centre is 6° below it.
+//!
+//! An hour counts as daylight between sunrise and sunset of its local day.
+//! Above the polar circles the sun may not cross the horizon at all; such days
+//! have no sunrise/sunset and are all day or all night.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::forecast_provider::{convert_timezone, LocalTimestamp, UtcTimestamp};
+
+/// Julian date of 2000-01-01 12:00 UTC
+const J2000: f64 = 2_451_545.0;
+
+/// Julian date of the Unix epoch
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+/// Axial tilt of the Earth in degrees
+const OBLIQUITY: f64 = 23.4397;
+
+/// Solar altitude at sunrise/sunset (refraction and the sun's radius)
+const SUNRISE_ALTITUDE: f64 = -0.833;
+
+/// Solar altitude at civil dawn/dusk
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// When the sun crosses a given altitude on one day
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Crossing {
+    Times {
+        rising: DateTime<Utc>,
+        setting: DateTime<Utc>,
+    },
+    /// The sun stays above the altitude all day
+    AlwaysAbove,
+    /// The sun stays below the altitude all day
+    AlwaysBelow,
+}
+
+/// Sun events of one local day; missing events did not happen that day
+#[derive(Debug, Clone, Serialize)]
+pub struct SunDay {
+    pub date: NaiveDate,
+
+    #[serde(rename = "civilDawn", skip_serializing_if = "Option::is_none")]
+    pub civil_dawn: Option<LocalTimestamp>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunrise: Option<LocalTimestamp>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<LocalTimestamp>,
+
+    #[serde(rename = "civilDusk", skip_serializing_if = "Option::is_none")]
+    pub civil_dusk: Option<LocalTimestamp>,
+}
+
+fn from_julian(jd: f64) -> DateTime<Utc> {
+    let seconds = ((jd - UNIX_EPOCH_JD) * 86_400.0).round() as i64;
+    DateTime::from_timestamp(seconds, 0).expect("Julian date within chrono's range")
+}
+
+/// Crossings of `altitude` (degrees) around solar noon of `date` at `lat`/`lng`
+fn crossing(date: NaiveDate, lat: f64, lng: f64, altitude: f64) -> Crossing {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date");
+    let days = (date - epoch).num_days() as f64;
+
+    // Mean solar noon, in days since J2000 (east longitudes are positive)
+    let mean_noon = days + 0.0008 - lng / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * mean_noon).rem_euclid(360.0).to_radians();
+    let center = 1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic_lng = (anomaly.to_degrees() + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+    let transit = J2000 + mean_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic_lng).sin();
+
+    let declination = (ecliptic_lng.sin() * OBLIQUITY.to_radians().sin()).asin();
+    let lat = lat.to_radians();
+    let cos_hour_angle = (altitude.to_radians().sin() - lat.sin() * declination.sin())
+        / (lat.cos() * declination.cos());
+
+    if cos_hour_angle > 1.0 {
+        return Crossing::AlwaysBelow;
+    }
+    if cos_hour_angle < -1.0 {
+        return Crossing::AlwaysAbove;
+    }
+
+    let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+    Crossing::Times {
+        rising: from_julian(transit - half_day),
+        setting: from_julian(transit + half_day),
+    }
+}
+
+/// Sunrise, sunset and civil twilight for local `date` at `lat`/`lng`
+pub fn sun_day(date: NaiveDate, lat: f64, lng: f64, tz: Tz) -> SunDay {
+    let local = |time: DateTime<Utc>| convert_timezone(UtcTimestamp(time), tz).ok();
+    let times = |altitude| match crossing(date, lat, lng, altitude) {
+        Crossing::Times { rising, setting } => (local(rising), local(setting)),
+        Crossing::AlwaysAbove | Crossing::AlwaysBelow => (None, None),
+    };
+
+    let (sunrise, sunset) = times(SUNRISE_ALTITUDE);
+    let (civil_dawn, civil_dusk) = times(CIVIL_TWILIGHT_ALTITUDE);
+
+    SunDay {
+        date,
+        civil_dawn,
+        sunrise,
+        sunset,
+        civil_dusk,
+    }
+}
+
+/// Whether the sun is up at `time`, judged against its local day in `tz`
+pub fn is_daylight(time: DateTime<Utc>, lat: f64, lng: f64, tz: Tz) -> bool {
+    let date = time.with_timezone(&tz).date_naive();
+    match crossing(date, lat, lng, SUNRISE_ALTITUDE) {
+        Crossing::Times { rising, setting } => rising <= time && time < setting,
+        Crossing::AlwaysAbove => true,
+        Crossing::AlwaysBelow => false,
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn assert_close(actual: DateTime<Utc>, expected: &str) {
+        let diff = (actual - utc(expected)).num_seconds().abs();
+        assert!(diff <= 120, "{} is {}s away from {}", actual, diff, expected);
+    }
+
+    fn times(date: (i32, u32, u32), lat: f64, lng: f64, altitude: f64) -> (DateTime<Utc>, DateTime<Utc>) {
+        let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+        match crossing(date, lat, lng, altitude) {
+            Crossing::Times { rising, setting } => (rising, setting),
+            other => panic!("expected sunrise and sunset, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_equator_at_equinox() {
+        // Solar noon 12:07 UTC (equation of time ≈ −7.5 min); refraction and
+        // the sun's radius lengthen the 12-hour day by about 7 minutes
+        let (sunrise, sunset) = times((2024, 3, 20), 0.0, 0.0, SUNRISE_ALTITUDE);
+        assert_close(sunrise, "2024-03-20T06:04:00Z");
+        assert_close(sunset, "2024-03-20T18:11:00Z");
+    }
+
+    #[test]
+    fn test_new_york_winter_with_civil_twilight() {
+        // Published: civil dawn 06:47, sunrise 07:17, sunset 16:32, civil dusk 17:02 EST
+        let (sunrise, sunset) = times((2024, 12, 21), 40.7128, -74.0060, SUNRISE_ALTITUDE);
+        assert_close(sunrise, "2024-12-21T12:17:00Z");
+        assert_close(sunset, "2024-12-21T21:32:00Z");
+
+        let (dawn, dusk) = times((2024, 12, 21), 40.7128, -74.0060, CIVIL_TWILIGHT_ALTITUDE);
+        assert_close(dawn, "2024-12-21T11:47:00Z");
+        assert_close(dusk, "2024-12-21T22:02:00Z");
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let tromso = (69.6492, 18.9553);
+        let midsummer = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let midwinter = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+
+        assert_eq!(crossing(midsummer, tromso.0, tromso.1, SUNRISE_ALTITUDE), Crossing::AlwaysAbove);
+        assert_eq!(crossing(midwinter, tromso.0, tromso.1, SUNRISE_ALTITUDE), Crossing::AlwaysBelow);
+
+        let day = sun_day(midwinter, tromso.0, tromso.1, chrono_tz::Europe::Oslo);
+        assert!(day.sunrise.is_none() && day.sunset.is_none());
+        assert!(day.civil_dawn.is_some(), "civil twilight still happens at noon");
+
+        assert!(is_daylight(utc("2024-06-21T23:00:00Z"), tromso.0, tromso.1, chrono_tz::Europe::Oslo));
+        assert!(!is_daylight(utc("2024-12-21T11:00:00Z"), tromso.0, tromso.1, chrono_tz::Europe::Oslo));
+    }
+
+    #[test]
+    fn test_is_daylight_uses_the_local_day() {
+        let jerusalem = chrono_tz::Asia::Jerusalem;
+        let (lat, lng) = (32.0853, 34.7818);
+        assert!(is_daylight(utc("2024-06-21T10:00:00Z"), lat, lng, jerusalem));
+        assert!(!is_daylight(utc("2024-06-21T02:00:00Z"), lat, lng, jerusalem));
+        assert!(!is_daylight(utc("2024-06-21T22:00:00Z"), lat, lng, jerusalem));
+    }
+}
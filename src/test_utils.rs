@@ -10,12 +10,21 @@
 //
 // It includes:
 // - Helper functions for creating valid Args structures
+// - Report and data point builders for the output format tests
 // - Mock API response builders for StormGlass and OpenWeatherMap
 // - Common test data and constants
 // - Assertion helpers for test validation
 
+use chrono::DateTime;
+use chrono_tz::Tz;
 use serde_json::json;
 use crate::args::Args;
+use crate::formats::OutputFormat;
+use crate::config::ResolvedConfig;
+use crate::config::types::ConfigSource;
+use crate::forecast_provider::{convert_timezone, UtcTimestamp, WeatherDataPoint};
+use crate::http::CacheSettings;
+use crate::report::{TransformedMetaData, TransformedWeatherResponse};
 
 // ============================================================================
 // Args Test Helpers
@@ -26,14 +35,29 @@ pub fn create_valid_args() -> Args {
     Args {
         days_ahead: 4,
         first_day_offset: 0,
-        provider: "stormglass".to_string(),
+        provider: Some("stormglass".to_string()),
         timezone: Some("UTC".to_string()),
         pick_timezone: false,
         list_providers: false,
         config_file_path: None,
+        spot: Vec::new(),
+        all_spots: false,
         lat: Some(32.486722),
         lng: Some(34.888722),
+        wind_unit: None,
+        temp_unit: None,
+        height_unit: None,
+        format: OutputFormat::Json,
+        drop_night: false,
+        tides: false,
+        no_cache: false,
+        refresh: false,
+        offline: false,
+        record: None,
+        replay: None,
+        ensemble: false,
         save: false,
+        env_file: None,
     }
 }
 
@@ -49,7 +73,7 @@ pub fn create_args_with_days(days_ahead: i32, first_day_offset: i32) -> Args {
 /// Create Args with custom provider
 pub fn create_args_with_provider(provider: &str) -> Args {
     Args {
-        provider: provider.to_string(),
+        provider: Some(provider.to_string()),
         ..create_valid_args()
     }
 }
@@ -71,6 +95,64 @@ pub fn create_args_with_timezone(timezone: &str) -> Args {
     }
 }
 
+// ============================================================================
+// ResolvedConfig Test Helpers
+// ============================================================================
+
+/// Create a valid ResolvedConfig matching `create_valid_args()`
+pub fn create_resolved_config() -> ResolvedConfig {
+    ResolvedConfig {
+        provider: "stormglass".to_string(),
+        provider_source: ConfigSource::Cli,
+        spot: None,
+        timezone: chrono_tz::UTC,
+        lat: 32.486722,
+        lng: 34.888722,
+        days_ahead: 4,
+        first_day_offset: 0,
+        units: Default::default(),
+        composite: Default::default(),
+        ensemble: Default::default(),
+        rider: Default::default(),
+        tides: false,
+        cache: CacheSettings::disabled(),
+        fixtures: Default::default(),
+        retry: Default::default(),
+        endpoints: Default::default(),
+    }
+}
+
+// ============================================================================
+// Report Test Helpers
+// ============================================================================
+
+/// Create an empty data point at Unix time `ts`, displayed in `tz`
+pub fn point_at(ts: i64, tz: Tz) -> WeatherDataPoint {
+    let utc = UtcTimestamp(DateTime::from_timestamp(ts, 0).unwrap());
+    WeatherDataPoint::empty(convert_timezone(utc, tz).unwrap())
+}
+
+/// Create a report of `hours` from provider "test", without sessions, tides
+/// or alerts
+pub fn report(hours: Vec<WeatherDataPoint>) -> TransformedWeatherResponse {
+    TransformedWeatherResponse {
+        hours,
+        sessions: Vec::new(),
+        tides: Vec::new(),
+        alerts: Vec::new(),
+        meta: TransformedMetaData {
+            lat: 0.0,
+            lng: 0.0,
+            start: String::new(),
+            end: String::new(),
+            report_generated_at: String::new(),
+            provider: "test".to_string(),
+            units: Default::default(),
+            sun: Vec::new(),
+        },
+    }
+}
+
 // ============================================================================
 // StormGlass Mock Response Builders
 // ============================================================================
@@ -261,7 +343,7 @@ mod tests {
         let args = create_valid_args();
         assert_eq!(args.days_ahead, 4);
         assert_eq!(args.first_day_offset, 0);
-        assert_eq!(args.provider, "stormglass");
+        assert_eq!(args.provider.as_deref(), Some("stormglass"));
         assert_eq!(args.lat, Some(32.486722));
         assert_eq!(args.lng, Some(34.888722));
     }
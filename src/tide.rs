@@ -0,0 +1,431 @@
+//! Offline tide prediction from harmonic constituents
+//!
+//! A spot's `tide_constituents` file lists the amplitude and Greenwich phase
+//! (κ′, the "GMT" phase on NOAA station pages) of each tidal constituent, as
+//! published for tide stations. The sea level relative to mean sea level is
+//!
+//! ```text
+//! h(t) = Σ f·A·cos(V(t) + u − κ′)
+//! ```
+//!
+//! where `V` is the constituent's astronomical argument at `t` and `f`/`u`
+//! are the nodal corrections for the 18.6-year lunar node cycle, both after
+//! Schureman's conventions as used by NOAA.
+//!
+//! # File Format
+//!
+//! Comma-separated, with a header row naming the columns. Only `Name`,
+//! `Amplitude` and `Phase` are required; columns are matched by the start of
+//! their header, so NOAA's harmonic constituent CSV export works as is:
+//!
+//! ```text
+//! Constituent #,Name,Amplitude (ft),Phase,Speed,Description
+//! 1,M2,1.51,220.3,28.984104,Principal lunar semidiurnal constituent
+//! 2,S2,0.35,245.1,30.0,Principal solar semidiurnal constituent
+//! ```
+//!
+//! Amplitudes are in meters unless the header says `ft`. When a `Speed`
+//! column is present it is checked against the constituent's known speed,
+//! which catches tables whose names or columns are mixed up.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use std::fs;
+use std::path::Path;
+
+use crate::forecast_provider::{convert_timezone, TideEvent, TideKind, UtcTimestamp};
+use crate::units::{Length, LengthUnit};
+
+/// Julian date of 2000-01-01 12:00 UTC, in seconds since the Unix epoch
+const J2000_UNIX: i64 = 946_728_000;
+
+/// Rates of the astronomical arguments T, s, h, p and p₁ in degrees per hour
+const RATES: [f64; 5] = [15.0, 0.549_016_53, 0.041_068_64, 0.004_641_83, 0.000_001_96];
+
+/// Largest accepted difference between a listed and the known speed (°/h)
+const SPEED_TOLERANCE: f64 = 0.001;
+
+/// Step used to search for high and low tides
+const EXTREMA_STEP_MINUTES: i64 = 1;
+
+/// Which nodal corrections apply to a constituent
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Nodal {
+    None,
+    M2,
+    /// `f` and `u` of M2 raised to the given power (compound tides)
+    M2Power(f64),
+    K1,
+    O1,
+    K2,
+    J1,
+    Mf,
+    Mm,
+}
+
+/// Multipliers of T, s, h, p and p₁ in the astronomical argument, its
+/// constant offset in degrees, and the nodal corrections
+struct Argument {
+    name: &'static str,
+    coefficients: [f64; 5],
+    offset: f64,
+    nodal: Nodal,
+}
+
+const fn arg(name: &'static str, coefficients: [f64; 5], offset: f64, nodal: Nodal) -> Argument {
+    Argument {
+        name,
+        coefficients,
+        offset,
+        nodal,
+    }
+}
+
+/// Supported constituents (Schureman, Table 2)
+const ARGUMENTS: &[Argument] = &[
+    // Semidiurnal
+    arg("M2", [2.0, -2.0, 2.0, 0.0, 0.0], 0.0, Nodal::M2),
+    arg("S2", [2.0, 0.0, 0.0, 0.0, 0.0], 0.0, Nodal::None),
+    arg("N2", [2.0, -3.0, 2.0, 1.0, 0.0], 0.0, Nodal::M2),
+    arg("K2", [2.0, 0.0, 2.0, 0.0, 0.0], 0.0, Nodal::K2),
+    arg("2N2", [2.0, -4.0, 2.0, 2.0, 0.0], 0.0, Nodal::M2),
+    arg("NU2", [2.0, -3.0, 4.0, -1.0, 0.0], 0.0, Nodal::M2),
+    arg("MU2", [2.0, -4.0, 4.0, 0.0, 0.0], 0.0, Nodal::M2),
+    arg("L2", [2.0, -1.0, 2.0, -1.0, 0.0], 180.0, Nodal::M2),
+    arg("LAM2", [2.0, -1.0, 0.0, 1.0, 0.0], 180.0, Nodal::M2),
+    arg("T2", [2.0, 0.0, -1.0, 0.0, 1.0], 0.0, Nodal::None),
+    // Diurnal
+    arg("K1", [1.0, 0.0, 1.0, 0.0, 0.0], -90.0, Nodal::K1),
+    arg("O1", [1.0, -2.0, 1.0, 0.0, 0.0], 90.0, Nodal::O1),
+    arg("P1", [1.0, 0.0, -1.0, 0.0, 0.0], 90.0, Nodal::None),
+    arg("Q1", [1.0, -3.0, 1.0, 1.0, 0.0], 90.0, Nodal::O1),
+    arg("RHO1", [1.0, -3.0, 3.0, -1.0, 0.0], 90.0, Nodal::O1),
+    arg("J1", [1.0, 1.0, 1.0, -1.0, 0.0], -90.0, Nodal::J1),
+    arg("S1", [1.0, 0.0, 0.0, 0.0, 0.0], 0.0, Nodal::None),
+    // Long period
+    arg("MM", [0.0, 1.0, 0.0, -1.0, 0.0], 0.0, Nodal::Mm),
+    arg("MF", [0.0, 2.0, 0.0, 0.0, 0.0], 0.0, Nodal::Mf),
+    arg("SA", [0.0, 0.0, 1.0, 0.0, 0.0], 0.0, Nodal::None),
+    arg("SSA", [0.0, 0.0, 2.0, 0.0, 0.0], 0.0, Nodal::None),
+    // Shallow water
+    arg("M3", [3.0, -3.0, 3.0, 0.0, 0.0], 0.0, Nodal::M2Power(1.5)),
+    arg("M4", [4.0, -4.0, 4.0, 0.0, 0.0], 0.0, Nodal::M2Power(2.0)),
+    arg("MN4", [4.0, -5.0, 4.0, 1.0, 0.0], 0.0, Nodal::M2Power(2.0)),
+    arg("MS4", [4.0, -2.0, 2.0, 0.0, 0.0], 0.0, Nodal::M2),
+    arg("S4", [4.0, 0.0, 0.0, 0.0, 0.0], 0.0, Nodal::None),
+    arg("M6", [6.0, -6.0, 6.0, 0.0, 0.0], 0.0, Nodal::M2Power(3.0)),
+    arg("S6", [6.0, 0.0, 0.0, 0.0, 0.0], 0.0, Nodal::None),
+    arg("M8", [8.0, -8.0, 8.0, 0.0, 0.0], 0.0, Nodal::M2Power(4.0)),
+];
+
+impl Argument {
+    fn speed(&self) -> f64 {
+        self.coefficients.iter().zip(RATES).map(|(c, rate)| c * rate).sum()
+    }
+}
+
+/// Mean longitudes T, s, h, p, p₁ and the lunar node N at `time`, in degrees
+fn astronomy(time: DateTime<Utc>) -> ([f64; 5], f64) {
+    let days = (time.timestamp() - J2000_UNIX) as f64 / 86_400.0;
+    let hours_of_day = (time.timestamp().rem_euclid(86_400)) as f64 / 3600.0;
+
+    let t = 180.0 + 15.0 * hours_of_day;
+    let s = 218.316_447_7 + 13.176_396_48 * days;
+    let h = 280.466_46 + 0.985_647_36 * days;
+    let p = 83.353_246_5 + 0.111_403_53 * days;
+    let p1 = 282.937_35 + 0.000_047_08 * days;
+    let node = 125.044_52 - 0.052_953_77 * days;
+
+    ([t, s, h, p, p1], node)
+}
+
+/// Nodal factor `f` and angle `u` (degrees) for lunar node longitude `node`
+fn nodal(kind: Nodal, node: f64) -> (f64, f64) {
+    let n = node.to_radians();
+    let (c1, c2, c3) = (n.cos(), (2.0 * n).cos(), (3.0 * n).cos());
+    let (s1, s2, s3) = (n.sin(), (2.0 * n).sin(), (3.0 * n).sin());
+
+    let m2 = (1.0004 - 0.0373 * c1 + 0.0002 * c2, -2.14 * s1);
+    match kind {
+        Nodal::None => (1.0, 0.0),
+        Nodal::M2 => m2,
+        Nodal::M2Power(power) => (m2.0.powf(power), m2.1 * power),
+        Nodal::K1 => (
+            1.0060 + 0.1150 * c1 - 0.0088 * c2 + 0.0006 * c3,
+            -8.86 * s1 + 0.68 * s2 - 0.07 * s3,
+        ),
+        Nodal::O1 => (
+            1.0089 + 0.1871 * c1 - 0.0147 * c2 + 0.0014 * c3,
+            10.80 * s1 - 1.34 * s2 + 0.19 * s3,
+        ),
+        Nodal::K2 => (
+            1.0241 + 0.2863 * c1 + 0.0083 * c2 - 0.0015 * c3,
+            -17.74 * s1 + 0.68 * s2 - 0.04 * s3,
+        ),
+        Nodal::J1 => (
+            1.1029 + 0.1676 * c1 - 0.0170 * c2 + 0.0016 * c3,
+            -12.94 * s1 + 1.34 * s2 - 0.19 * s3,
+        ),
+        Nodal::Mf => (
+            1.0429 + 0.4135 * c1 - 0.004 * c2,
+            -23.74 * s1 + 2.68 * s2 - 0.38 * s3,
+        ),
+        Nodal::Mm => (1.0 - 0.1300 * c1 + 0.0013 * c2, 0.0),
+    }
+}
+
+/// One row of a constituent table
+#[derive(Debug, Clone, PartialEq)]
+struct Constituent {
+    argument: usize,
+    /// Amplitude in meters
+    amplitude: f64,
+    /// Greenwich phase lag κ′ in degrees
+    phase: f64,
+}
+
+/// Harmonic constants of one tide station
+#[derive(Debug, Clone, PartialEq)]
+pub struct TideTable {
+    constituents: Vec<Constituent>,
+}
+
+impl TideTable {
+    /// Read a constituent table from `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read tide constituents: {}", path.display()))?;
+        Self::parse(&contents)
+            .with_context(|| format!("Invalid tide constituents file: {}", path.display()))
+    }
+
+    /// Parse a constituent table (see the module docs for the format)
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut lines = contents
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let Some((_, header)) = lines.next() else {
+            anyhow::bail!("The file is empty; expected a header row with Name, Amplitude and Phase");
+        };
+        let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+        let column = |prefix: &str| columns.iter().position(|c| c.starts_with(prefix));
+
+        let name_col = column("name")
+            .or_else(|| column("constituent"))
+            .context("Missing Name column")?;
+        let amplitude_col = column("amplitude").context("Missing Amplitude column")?;
+        let phase_col = column("phase").context("Missing Phase column")?;
+        let speed_col = column("speed");
+
+        let amplitude_header = &columns[amplitude_col];
+        let amplitude_unit = if amplitude_header.contains("ft") || amplitude_header.contains("feet") {
+            LengthUnit::Feet
+        } else {
+            LengthUnit::Meters
+        };
+
+        let mut constituents = Vec::new();
+        for (index, line) in lines {
+            let line_no = index + 1;
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |col: usize| {
+                fields
+                    .get(col)
+                    .copied()
+                    .with_context(|| format!("Line {}: expected at least {} columns", line_no, col + 1))
+            };
+            let number = |col: usize| -> Result<f64> {
+                let value = field(col)?;
+                value
+                    .parse()
+                    .with_context(|| format!("Line {}: '{}' is not a number", line_no, value))
+            };
+
+            let name = field(name_col)?;
+            let argument = ARGUMENTS
+                .iter()
+                .position(|a| a.name.eq_ignore_ascii_case(name))
+                .with_context(|| {
+                    let known: Vec<&str> = ARGUMENTS.iter().map(|a| a.name).collect();
+                    format!(
+                        "Line {}: unsupported constituent '{}'. Supported constituents: {}",
+                        line_no,
+                        name,
+                        known.join(", ")
+                    )
+                })?;
+
+            if let Some(col) = speed_col {
+                let listed = number(col)?;
+                let known = ARGUMENTS[argument].speed();
+                if (listed - known).abs() > SPEED_TOLERANCE {
+                    anyhow::bail!(
+                        "Line {}: {} has speed {}°/h, expected {:.7}°/h",
+                        line_no,
+                        name,
+                        listed,
+                        known
+                    );
+                }
+            }
+
+            let amplitude = Length::new(number(amplitude_col)?, amplitude_unit)
+                .to(LengthUnit::Meters)
+                .value();
+            constituents.push(Constituent {
+                argument,
+                amplitude,
+                phase: number(phase_col)?,
+            });
+        }
+
+        if constituents.is_empty() {
+            anyhow::bail!("No constituents listed");
+        }
+        Ok(Self { constituents })
+    }
+
+    /// Predicted sea level relative to mean sea level at `time`
+    pub fn predict(&self, time: DateTime<Utc>) -> Length {
+        let (longitudes, node) = astronomy(time);
+
+        let height: f64 = self
+            .constituents
+            .iter()
+            .map(|c| {
+                let argument = &ARGUMENTS[c.argument];
+                let v: f64 = argument
+                    .coefficients
+                    .iter()
+                    .zip(longitudes)
+                    .map(|(k, longitude)| k * longitude)
+                    .sum::<f64>()
+                    + argument.offset;
+                let (f, u) = nodal(argument.nodal, node);
+                f * c.amplitude * (v + u - c.phase).to_radians().cos()
+            })
+            .sum();
+
+        Length::new(height, LengthUnit::Meters)
+    }
+
+    /// High and low tides strictly between `start` and `end`, to the minute
+    pub fn extremes(&self, start: DateTime<Utc>, end: DateTime<Utc>, tz: Tz) -> Vec<TideEvent> {
+        let step = Duration::minutes(EXTREMA_STEP_MINUTES);
+        let mut samples = Vec::new();
+        let mut time = start;
+        while time <= end {
+            samples.push((time, self.predict(time)));
+            time += step;
+        }
+
+        samples
+            .windows(3)
+            .filter_map(|w| {
+                let (before, (time, height), after) = (w[0].1.value(), w[1], w[2].1.value());
+                let kind = if height.value() > before && height.value() >= after {
+                    TideKind::High
+                } else if height.value() < before && height.value() <= after {
+                    TideKind::Low
+                } else {
+                    return None;
+                };
+                let time = convert_timezone(UtcTimestamp(time), tz).ok()?;
+                Some(TideEvent { time, height, kind })
+            })
+            .collect()
+    }
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_known_speeds_match_published_values() {
+        let speed = |name: &str| ARGUMENTS.iter().find(|a| a.name == name).unwrap().speed();
+        assert!((speed("M2") - 28.984_104_2).abs() < 1e-6);
+        assert!((speed("S2") - 30.0).abs() < 1e-6);
+        assert!((speed("K1") - 15.041_068_6).abs() < 1e-6);
+        assert!((speed("O1") - 13.943_035_6).abs() < 1e-6);
+        assert!((speed("MN4") - 57.423_833_7).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_parse_noaa_export_in_feet() {
+        let table = TideTable::parse(
+            "Constituent #,Name,Amplitude (ft),Phase,Speed,Description\n\
+             1,M2,1.0,220.3,28.984104,Principal lunar semidiurnal constituent\n\
+             2,S2,0.5,245.1,30.0,Principal solar semidiurnal constituent\n",
+        )
+        .unwrap();
+
+        assert_eq!(table.constituents.len(), 2);
+        assert!((table.constituents[0].amplitude - 0.3048).abs() < 1e-6);
+        assert_eq!(table.constituents[1].phase, 245.1);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_tables() {
+        let unknown = TideTable::parse("Name,Amplitude,Phase\nXYZ9,0.1,10\n").unwrap_err();
+        assert!(format!("{:#}", unknown).contains("unsupported constituent 'XYZ9'"));
+
+        let mixed_up = TideTable::parse("Name,Amplitude,Phase,Speed\nK1,0.1,10,13.9430356\n");
+        assert!(mixed_up.is_err(), "O1 speed listed for K1");
+
+        assert!(TideTable::parse("Name,Amplitude\nM2,0.1\n").is_err());
+        assert!(TideTable::parse("Name,Amplitude,Phase\n").is_err());
+    }
+
+    #[test]
+    fn test_s2_peaks_at_greenwich_midnight_and_noon() {
+        // S2 has no nodal correction and V = 2T, so with zero phase the
+        // sea level peaks whenever the mean sun crosses Greenwich's meridians
+        let table = TideTable::parse("Name,Amplitude,Phase\nS2,0.5,0\n").unwrap();
+
+        assert!((table.predict(utc("2024-05-01T00:00:00Z")).value() - 0.5).abs() < 1e-9);
+        assert!(table.predict(utc("2024-05-01T03:00:00Z")).value().abs() < 1e-9);
+        assert!((table.predict(utc("2024-05-01T06:00:00Z")).value() + 0.5).abs() < 1e-9);
+
+        let events = table.extremes(
+            utc("2024-05-01T01:00:00Z"),
+            utc("2024-05-02T01:00:00Z"),
+            chrono_tz::UTC,
+        );
+        let summary: Vec<(String, TideKind)> = events
+            .iter()
+            .map(|e| (e.time.local().format("%H:%M").to_string(), e.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("06:00".to_string(), TideKind::Low),
+                ("12:00".to_string(), TideKind::High),
+                ("18:00".to_string(), TideKind::Low),
+                ("00:00".to_string(), TideKind::High),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_m2_repeats_every_lunar_half_day() {
+        let table = TideTable::parse("Name,Amplitude,Phase\nM2,1.0,120\n").unwrap();
+        let start = utc("2024-05-01T00:00:00Z");
+        // 360° / 28.9841042°/h = 12h 25m 14s
+        let period = Duration::seconds(44_714);
+
+        let a = table.predict(start).value();
+        let b = table.predict(start + period).value();
+        assert!((a - b).abs() < 1e-3, "{} vs {}", a, b);
+    }
+}
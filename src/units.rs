@@ -0,0 +1,419 @@
+//! Typed physical quantities
+//!
+//! Weather values carry the unit they were produced in, so conversions are
+//! explicit and output metadata is derived from the data instead of guessed
+//! from the provider name.
+//!
+//! A [`Quantity`] serializes as its bare numeric value; the unit is reported
+//! separately in the output's `meta.units` block.
+//!
+//! [`OutputUnits`] holds the units selected by the user (`--wind-unit`,
+//! `--temp-unit`, `--height-unit`); every forecast is converted to them
+//! before it is written.
+
+use anyhow::Result;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Convert m/s to knots
+pub const MS_TO_KNOTS: f64 = 1.94384;
+
+const MS_TO_KMH: f64 = 3.6;
+const MS_TO_MPH: f64 = 2.236_936;
+const METERS_TO_FEET: f64 = 3.280_84;
+
+/// Upper bound in m/s of Beaufort forces 0 to 11 (WMO scale)
+const BEAUFORT_LIMITS: [f64; 12] = [
+    0.5, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7,
+];
+
+/// A unit of measurement for one physical dimension
+///
+/// Conversions go through the dimension's base unit (m/s, m, °C, °, s).
+pub trait Unit: Copy + PartialEq + fmt::Debug {
+    /// Convert `value` in this unit to the base unit
+    fn convert_to_base(self, value: f64) -> f64;
+
+    /// Convert `value` in the base unit to this unit
+    fn convert_from_base(self, value: f64) -> f64;
+
+    /// Short display symbol (e.g. "m/s", "°C")
+    fn symbol(self) -> &'static str;
+}
+
+/// A numeric value tagged with its unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity<U: Unit> {
+    value: f64,
+    unit: U,
+}
+
+impl<U: Unit> Quantity<U> {
+    pub fn new(value: f64, unit: U) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> U {
+        self.unit
+    }
+
+    /// Convert to another unit of the same dimension
+    pub fn to(self, unit: U) -> Self {
+        if unit == self.unit {
+            return self;
+        }
+        Self::new(unit.convert_from_base(self.unit.convert_to_base(self.value)), unit)
+    }
+}
+
+impl<U: Unit> Serialize for Quantity<U> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.value)
+    }
+}
+
+impl<U: Unit> fmt::Display for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1} {}", self.value, self.unit.symbol())
+    }
+}
+
+// ============================================================================
+// Speed
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedUnit {
+    MetersPerSecond,
+    Knots,
+    KilometersPerHour,
+    MilesPerHour,
+    /// Beaufort force (0-12); converting to it rounds to a whole force
+    Beaufort,
+}
+
+impl Unit for SpeedUnit {
+    fn convert_to_base(self, value: f64) -> f64 {
+        match self {
+            SpeedUnit::MetersPerSecond => value,
+            SpeedUnit::Knots => value / MS_TO_KNOTS,
+            SpeedUnit::KilometersPerHour => value / MS_TO_KMH,
+            SpeedUnit::MilesPerHour => value / MS_TO_MPH,
+            SpeedUnit::Beaufort => beaufort_to_ms(value),
+        }
+    }
+
+    fn convert_from_base(self, value: f64) -> f64 {
+        match self {
+            SpeedUnit::MetersPerSecond => value,
+            SpeedUnit::Knots => value * MS_TO_KNOTS,
+            SpeedUnit::KilometersPerHour => value * MS_TO_KMH,
+            SpeedUnit::MilesPerHour => value * MS_TO_MPH,
+            SpeedUnit::Beaufort => ms_to_beaufort(value),
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            SpeedUnit::MetersPerSecond => "m/s",
+            SpeedUnit::Knots => "knots",
+            SpeedUnit::KilometersPerHour => "km/h",
+            SpeedUnit::MilesPerHour => "mph",
+            SpeedUnit::Beaufort => "Bft",
+        }
+    }
+}
+
+impl FromStr for SpeedUnit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "m/s" | "ms" | "mps" => Ok(SpeedUnit::MetersPerSecond),
+            "knots" | "knot" | "kn" | "kt" | "kts" => Ok(SpeedUnit::Knots),
+            "km/h" | "kmh" | "kph" => Ok(SpeedUnit::KilometersPerHour),
+            "mph" => Ok(SpeedUnit::MilesPerHour),
+            "bft" | "beaufort" => Ok(SpeedUnit::Beaufort),
+            _ => anyhow::bail!(
+                "Unknown wind unit '{}'. Valid units: knots, m/s, km/h, mph, beaufort",
+                s
+            ),
+        }
+    }
+}
+
+pub type Speed = Quantity<SpeedUnit>;
+
+fn ms_to_beaufort(ms: f64) -> f64 {
+    BEAUFORT_LIMITS.iter().take_while(|&&limit| ms >= limit).count() as f64
+}
+
+/// Midpoint of the force's wind speed range (lower bound for force 12)
+fn beaufort_to_ms(force: f64) -> f64 {
+    let force = force.round().clamp(0.0, 12.0) as usize;
+    let lower = if force == 0 { 0.0 } else { BEAUFORT_LIMITS[force - 1] };
+    match BEAUFORT_LIMITS.get(force) {
+        Some(upper) => (lower + upper) / 2.0,
+        None => lower,
+    }
+}
+
+// ============================================================================
+// Length
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthUnit {
+    Meters,
+    Feet,
+}
+
+impl Unit for LengthUnit {
+    fn convert_to_base(self, value: f64) -> f64 {
+        match self {
+            LengthUnit::Meters => value,
+            LengthUnit::Feet => value / METERS_TO_FEET,
+        }
+    }
+
+    fn convert_from_base(self, value: f64) -> f64 {
+        match self {
+            LengthUnit::Meters => value,
+            LengthUnit::Feet => value * METERS_TO_FEET,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Meters => "m",
+            LengthUnit::Feet => "ft",
+        }
+    }
+}
+
+impl FromStr for LengthUnit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "m" | "meter" | "meters" => Ok(LengthUnit::Meters),
+            "ft" | "foot" | "feet" => Ok(LengthUnit::Feet),
+            _ => anyhow::bail!("Unknown height unit '{}'. Valid units: m, ft", s),
+        }
+    }
+}
+
+pub type Length = Quantity<LengthUnit>;
+
+// ============================================================================
+// Temperature
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl Unit for TemperatureUnit {
+    fn convert_to_base(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+            TemperatureUnit::Kelvin => value - 273.15,
+        }
+    }
+
+    fn convert_from_base(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Fahrenheit => value * 9.0 / 5.0 + 32.0,
+            TemperatureUnit::Kelvin => value + 273.15,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+            TemperatureUnit::Kelvin => "K",
+        }
+    }
+}
+
+impl FromStr for TemperatureUnit {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().trim_start_matches('°').to_lowercase().as_str() {
+            "c" | "celsius" => Ok(TemperatureUnit::Celsius),
+            "f" | "fahrenheit" => Ok(TemperatureUnit::Fahrenheit),
+            _ => anyhow::bail!("Unknown temperature unit '{}'. Valid units: C, F", s),
+        }
+    }
+}
+
+pub type Temperature = Quantity<TemperatureUnit>;
+
+// ============================================================================
+// Angle and Period
+// ============================================================================
+
+/// Directions are always compass degrees (0° = from north, clockwise)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleUnit {
+    Degrees,
+}
+
+impl Unit for AngleUnit {
+    fn convert_to_base(self, value: f64) -> f64 {
+        value
+    }
+
+    fn convert_from_base(self, value: f64) -> f64 {
+        value
+    }
+
+    fn symbol(self) -> &'static str {
+        "°"
+    }
+}
+
+pub type Angle = Quantity<AngleUnit>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodUnit {
+    Seconds,
+}
+
+impl Unit for PeriodUnit {
+    fn convert_to_base(self, value: f64) -> f64 {
+        value
+    }
+
+    fn convert_from_base(self, value: f64) -> f64 {
+        value
+    }
+
+    fn symbol(self) -> &'static str {
+        "s"
+    }
+}
+
+pub type Period = Quantity<PeriodUnit>;
+
+// ============================================================================
+// Output Units
+// ============================================================================
+
+/// Units every forecast is converted to before output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputUnits {
+    pub wind: SpeedUnit,
+    pub temperature: TemperatureUnit,
+    pub height: LengthUnit,
+}
+
+impl Default for OutputUnits {
+    fn default() -> Self {
+        Self {
+            wind: SpeedUnit::Knots,
+            temperature: TemperatureUnit::Celsius,
+            height: LengthUnit::Meters,
+        }
+    }
+}
+
+impl fmt::Display for OutputUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}",
+            self.wind.symbol(),
+            self.temperature.symbol(),
+            self.height.symbol()
+        )
+    }
+}
+
+/// Compass degrees (0° = north)
+pub fn degrees(value: f64) -> Angle {
+    Angle::new(value, AngleUnit::Degrees)
+}
+
+pub fn seconds(value: f64) -> Period {
+    Period::new(value, PeriodUnit::Seconds)
+}
+
+// tests
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_conversions_round_trip() {
+        let speed = Speed::new(10.0, SpeedUnit::MetersPerSecond);
+        assert!((speed.to(SpeedUnit::Knots).value() - 19.4384).abs() < 1e-9);
+        assert!((speed.to(SpeedUnit::KilometersPerHour).value() - 36.0).abs() < 1e-9);
+
+        let back = speed.to(SpeedUnit::MilesPerHour).to(SpeedUnit::MetersPerSecond);
+        assert!((back.value() - 10.0).abs() < 1e-9);
+        assert_eq!(back.unit(), SpeedUnit::MetersPerSecond);
+    }
+
+    #[test]
+    fn test_beaufort_scale() {
+        let force = |ms: f64| Speed::new(ms, SpeedUnit::MetersPerSecond).to(SpeedUnit::Beaufort).value();
+        assert_eq!(force(0.2), 0.0);
+        assert_eq!(force(5.5), 4.0, "lower bound belongs to the higher force");
+        assert_eq!(force(10.0), 5.0);
+        assert_eq!(force(40.0), 12.0);
+
+        let back = Speed::new(4.0, SpeedUnit::Beaufort).to(SpeedUnit::MetersPerSecond);
+        assert_eq!(back.value(), 6.75);
+    }
+
+    #[test]
+    fn test_temperature_conversions() {
+        let kelvin = Temperature::new(293.15, TemperatureUnit::Kelvin);
+        assert!((kelvin.to(TemperatureUnit::Celsius).value() - 20.0).abs() < 1e-9);
+        assert!((kelvin.to(TemperatureUnit::Fahrenheit).value() - 68.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_length_conversion() {
+        let height = Length::new(1.0, LengthUnit::Meters);
+        assert!((height.to(LengthUnit::Feet).value() - 3.28084).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_unit_names_and_symbols() {
+        assert_eq!("kts".parse::<SpeedUnit>().unwrap(), SpeedUnit::Knots);
+        assert_eq!("Beaufort".parse::<SpeedUnit>().unwrap(), SpeedUnit::Beaufort);
+        assert_eq!("°F".parse::<TemperatureUnit>().unwrap(), TemperatureUnit::Fahrenheit);
+        assert_eq!("feet".parse::<LengthUnit>().unwrap(), LengthUnit::Feet);
+        assert!("furlongs/fortnight".parse::<SpeedUnit>().is_err());
+        assert!("K".parse::<TemperatureUnit>().is_err());
+
+        // Symbols round-trip so saved config values parse back
+        for unit in [SpeedUnit::MetersPerSecond, SpeedUnit::KilometersPerHour, SpeedUnit::Beaufort] {
+            assert_eq!(unit.symbol().parse::<SpeedUnit>().unwrap(), unit);
+        }
+    }
+
+    #[test]
+    fn test_quantity_serializes_as_bare_number() {
+        let json = serde_json::to_string(&Speed::new(5.5, SpeedUnit::Knots)).unwrap();
+        assert_eq!(json, "5.5");
+    }
+}
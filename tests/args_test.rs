@@ -161,4 +161,49 @@ fn test_args_stores_coordinates_without_range_validation() {
     // This is intentional - validation happens in config layer
     let args = create_args_with_coordinates(100.0, 200.0);
     assert!(validate_args(&args).is_ok(), "Args layer accepts any coordinates");
-}
\ No newline at end of file
+}
+#[test]
+fn test_multiple_spots_reject_ensemble_and_save() {
+    let mut args = create_valid_args();
+    args.spot = vec!["bat-galim".to_string(), "hilton".to_string()];
+    assert!(args.is_multi_spot());
+    assert!(validate_args(&args).is_ok());
+
+    args.ensemble = true;
+    let err_msg = validate_args(&args).unwrap_err().to_string();
+    assert!(err_msg.contains("--ensemble"));
+
+    args.ensemble = false;
+    args.all_spots = true;
+    args.spot.clear();
+    args.save = true;
+    let err_msg = validate_args(&args).unwrap_err().to_string();
+    assert!(err_msg.contains("--save"));
+}
+
+#[test]
+fn test_ensemble_rejects_non_json_format() {
+    use windsurf_forecast::formats::OutputFormat;
+
+    let mut args = create_valid_args();
+    args.ensemble = true;
+    assert!(validate_args(&args).is_ok());
+
+    args.format = OutputFormat::Csv;
+    let err_msg = validate_args(&args).unwrap_err().to_string();
+    assert!(err_msg.contains("--format json"));
+}
+
+#[test]
+fn test_multiple_spots_reject_table_format() {
+    use windsurf_forecast::formats::OutputFormat;
+
+    let mut args = create_valid_args();
+    args.spot = vec!["bat-galim".to_string(), "hilton".to_string()];
+    args.format = OutputFormat::Csv;
+    assert!(validate_args(&args).is_ok());
+
+    args.format = OutputFormat::Table;
+    let err_msg = validate_args(&args).unwrap_err().to_string();
+    assert!(err_msg.contains("--format table"));
+}
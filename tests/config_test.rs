@@ -4,13 +4,26 @@
 //
 // Tests for configuration file handling, precedence rules, and validation.
 
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
 use tempfile::NamedTempFile;
 use windsurf_forecast::config::loader::{
-    get_default_config_path, load_config_from_file, save_config, Config, GeneralConfig,
+    get_default_config_path, load_config_from_file, save_config, CacheConfig, Config,
+    GeneralConfig, HttpConfig, RiderConfig, SkillLevel,
+};
+use windsurf_forecast::config::resolver::{
+    resolve_cache_settings, resolve_coordinates, resolve_endpoints, resolve_retry_policy, resolve_from_args_and_file, resolve_output_units,
+    resolve_spot,
+    resolve_spots_from_args_and_file, validate_coordinates, validate_rider_config,
 };
-use windsurf_forecast::config::resolver::{resolve_coordinates, validate_coordinates};
 use windsurf_forecast::config::timezone::TimezoneConfig;
+use windsurf_forecast::config::types::ConfigSource;
+use windsurf_forecast::config::save_config_from_resolved;
+use windsurf_forecast::http::CacheMode;
+use windsurf_forecast::test_utils::create_valid_args;
+use windsurf_forecast::units::{LengthUnit, OutputUnits, SpeedUnit, TemperatureUnit};
 
 // ============================================================================
 // Test Pattern 1: Config File Loading
@@ -237,7 +250,9 @@ fn test_save_config_persists_to_file() {
             default_provider: "stormglass".to_string(),
             lat: Some(32.486722),
             lng: Some(34.888722),
+            ..Default::default()
         },
+        ..Default::default()
     };
 
     let result = save_config(&config, Some(&temp_file.path().to_path_buf()));
@@ -263,7 +278,9 @@ fn test_save_load_roundtrip_preserves_values() {
             default_provider: "openweathermap".to_string(),
             lat: Some(51.5074),
             lng: Some(-0.1278),
+            ..Default::default()
         },
+        ..Default::default()
     };
 
     save_config(&original, Some(&temp_file.path().to_path_buf())).unwrap();
@@ -576,7 +593,9 @@ fn test_config_serialization_format() {
             default_provider: "stormglass".to_string(),
             lat: Some(32.486722),
             lng: Some(34.888722),
+            ..Default::default()
         },
+        ..Default::default()
     };
 
     let toml_string = toml::to_string_pretty(&config);
@@ -608,3 +627,476 @@ timezone = "UTC"
     assert!(config.general.lng.is_none());
     assert_eq!(config.general.default_provider, "stormglass");
 }
+
+// ============================================================================
+// Test Pattern 8: Output Units
+// ============================================================================
+
+#[test]
+fn test_output_units_default_to_knots_celsius_meters() {
+    let units = resolve_output_units(&create_valid_args(), &Config::default()).unwrap();
+    assert_eq!(units, OutputUnits::default());
+    assert_eq!(units.wind, SpeedUnit::Knots);
+    assert_eq!(units.temperature, TemperatureUnit::Celsius);
+    assert_eq!(units.height, LengthUnit::Meters);
+}
+
+#[test]
+fn test_cli_units_override_config_file_units() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[general]
+wind_unit = "beaufort"
+temp_unit = "F"
+height_unit = "ft"
+    "#
+    )
+    .unwrap();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let mut args = create_valid_args();
+    args.wind_unit = Some("km/h".to_string());
+
+    let units = resolve_output_units(&args, &config).unwrap();
+    assert_eq!(units.wind, SpeedUnit::KilometersPerHour, "CLI wins");
+    assert_eq!(units.temperature, TemperatureUnit::Fahrenheit);
+    assert_eq!(units.height, LengthUnit::Feet);
+}
+
+#[test]
+fn test_invalid_unit_returns_helpful_error() {
+    let mut args = create_valid_args();
+    args.height_unit = Some("yards".to_string());
+
+    let err = resolve_output_units(&args, &Config::default()).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("Invalid height unit"));
+    assert!(message.contains("Valid units: m, ft"));
+}
+
+// ============================================================================
+// Test Pattern 9: Named Spots
+// ============================================================================
+
+fn write_spots_config() -> NamedTempFile {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[general]
+lat = 32.486722
+lng = 34.888722
+timezone = "UTC"
+
+[spots.bat-galim]
+lat = 32.8329
+lng = 34.9547
+timezone = "Asia/Jerusalem"
+provider = "openmeteo"
+notes = "Rocks at low tide"
+
+[spots.broken]
+lat = 123.0
+lng = 34.0
+
+[spots.backwards]
+lat = 32.0
+lng = 34.0
+shore_bearing = 400
+    "#
+    )
+    .unwrap();
+    temp_file
+}
+
+#[test]
+fn test_spot_supplies_coordinates_timezone_and_provider() {
+    let temp_file = write_spots_config();
+    let mut args = create_valid_args();
+    args.config_file_path = Some(temp_file.path().to_path_buf());
+    args.spot = vec!["bat-galim".to_string()];
+    args.lat = None;
+    args.lng = None;
+    args.timezone = None;
+    args.provider = None;
+
+    let resolved = resolve_from_args_and_file(&args).unwrap();
+    assert_eq!((resolved.lat, resolved.lng), (32.8329, 34.9547));
+    assert_eq!(resolved.timezone, chrono_tz::Asia::Jerusalem);
+    assert_eq!(resolved.provider, "openmeteo");
+    let spot = resolved.spot.expect("spot should be recorded");
+    assert_eq!(spot.name, "bat-galim");
+    assert_eq!(spot.config.notes.as_deref(), Some("Rocks at low tide"));
+}
+
+#[test]
+fn test_cli_provider_and_timezone_override_spot() {
+    let temp_file = write_spots_config();
+    let mut args = create_valid_args();
+    args.config_file_path = Some(temp_file.path().to_path_buf());
+    args.spot = vec!["bat-galim".to_string()];
+    args.lat = None;
+    args.lng = None;
+
+    let resolved = resolve_from_args_and_file(&args).unwrap();
+    assert_eq!(resolved.provider, "stormglass");
+    assert_eq!(resolved.timezone, chrono_tz::UTC);
+    assert_eq!((resolved.lat, resolved.lng), (32.8329, 34.9547));
+}
+
+#[test]
+fn test_saving_spot_run_keeps_general_provider() {
+    let temp_file = write_spots_config();
+    let path = temp_file.path().to_path_buf();
+    let default_provider = load_config_from_file(Some(&path)).unwrap().general.default_provider;
+    let mut args = create_valid_args();
+    args.config_file_path = Some(path.clone());
+    args.spot = vec!["bat-galim".to_string()];
+    args.lat = None;
+    args.lng = None;
+    args.provider = None;
+
+    // The file sets no default_provider, so without the spot it is the built-in one
+    args.spot = Vec::new();
+    assert_eq!(resolve_from_args_and_file(&args).unwrap().provider_source, ConfigSource::Default);
+    args.spot = vec!["bat-galim".to_string()];
+
+    let resolved = resolve_from_args_and_file(&args).unwrap();
+    assert_eq!(resolved.provider, "openmeteo");
+    assert_eq!(resolved.provider_source, ConfigSource::ConfigFile);
+    save_config_from_resolved(&resolved, Some(&path)).unwrap();
+
+    let saved = load_config_from_file(Some(&path)).unwrap();
+    assert_eq!(saved.general.default_provider, default_provider);
+    assert_eq!((saved.general.lat, saved.general.lng), (Some(32.486722), Some(34.888722)));
+    assert_eq!(saved.spots["bat-galim"].provider.as_deref(), Some("openmeteo"));
+
+    // An explicit --provider is still saved
+    args.provider = Some("windy".to_string());
+    let resolved = resolve_from_args_and_file(&args).unwrap();
+    assert_eq!(resolved.provider_source, ConfigSource::Cli);
+    save_config_from_resolved(&resolved, Some(&path)).unwrap();
+    assert_eq!(load_config_from_file(Some(&path)).unwrap().general.default_provider, "windy");
+}
+
+#[test]
+fn test_unknown_spot_lists_configured_spots() {
+    let temp_file = write_spots_config();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let err = resolve_spot("hilton", &config, temp_file.path().parent().unwrap()).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("Unknown spot 'hilton'"));
+    assert!(message.contains("backwards, bat-galim, broken"));
+}
+
+#[test]
+fn test_spot_with_invalid_coordinates_returns_error() {
+    let temp_file = write_spots_config();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let err = resolve_spot("broken", &config, temp_file.path().parent().unwrap()).unwrap_err();
+    assert!(format!("{:#}", err).contains("Invalid coordinates for spot 'broken'"));
+}
+
+#[test]
+fn test_resolve_listed_spots_in_order() {
+    let temp_file = write_spots_config();
+    let mut args = create_valid_args();
+    args.config_file_path = Some(temp_file.path().to_path_buf());
+    args.spot = vec!["bat-galim".to_string(), "bat-galim".to_string()];
+    args.lat = None;
+    args.lng = None;
+
+    let resolved = resolve_spots_from_args_and_file(&args).unwrap();
+    assert_eq!(resolved.len(), 2);
+    assert!(resolved.iter().all(|c| c.lat == 32.8329));
+
+    assert!(
+        resolve_from_args_and_file(&args).is_err(),
+        "single-config resolution refuses several spots"
+    );
+}
+
+#[test]
+fn test_all_spots_fails_on_any_invalid_spot() {
+    let temp_file = write_spots_config();
+    let mut args = create_valid_args();
+    args.config_file_path = Some(temp_file.path().to_path_buf());
+    args.all_spots = true;
+    args.lat = None;
+    args.lng = None;
+
+    let err = resolve_spots_from_args_and_file(&args).unwrap_err();
+    assert!(format!("{:#}", err).contains("spot 'backwards'"));
+}
+
+#[test]
+fn test_all_spots_without_spots_returns_error() {
+    let mut args = create_valid_args();
+    args.config_file_path = Some(std::path::PathBuf::from(
+        "/tmp/nonexistent_windsurf_config_spots.toml",
+    ));
+    args.all_spots = true;
+
+    let err = resolve_spots_from_args_and_file(&args).unwrap_err();
+    assert!(format!("{:#}", err).contains("No spots to fetch"));
+}
+
+#[test]
+fn test_spot_with_out_of_range_shore_bearing_returns_error() {
+    let temp_file = write_spots_config();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let err = resolve_spot("backwards", &config, temp_file.path().parent().unwrap()).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("Invalid shore_bearing for spot 'backwards'"));
+    assert!(message.contains("400"));
+}
+
+#[test]
+fn test_spot_loads_tide_constituents() {
+    // Relative paths are resolved against the config file, not the working directory
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("tides")).unwrap();
+    std::fs::write(
+        dir.path().join("tides/haifa.csv"),
+        "Name,Amplitude,Phase\nM2,0.15,300.2\nS2,0.06,320.5\n",
+    )
+    .unwrap();
+
+    let path = dir.path().join("config.toml");
+    std::fs::write(
+        &path,
+        r#"
+[spots.haifa]
+lat = 32.8329
+lng = 34.9547
+tide_constituents = "tides/haifa.csv"
+
+[spots.missing]
+lat = 32.0
+lng = 34.0
+tide_constituents = "/nonexistent/tides.csv"
+    "#,
+    )
+    .unwrap();
+    let config = load_config_from_file(Some(&path)).unwrap();
+
+    let spot = resolve_spot("haifa", &config, dir.path()).unwrap();
+    assert!(spot.tide_table.is_some());
+
+    let mut args = create_valid_args();
+    args.config_file_path = Some(path);
+    args.spot = vec!["haifa".to_string()];
+    args.lat = None;
+    args.lng = None;
+    let resolved = resolve_from_args_and_file(&args).unwrap();
+    assert!(resolved.spot.unwrap().tide_table.is_some());
+
+    let err = resolve_spot("missing", &config, dir.path()).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("Invalid tide_constituents for spot 'missing'"));
+    assert!(message.contains("/nonexistent/tides.csv"));
+}
+
+// ============================================================================
+// Test Pattern 10: Rider Thresholds
+// ============================================================================
+
+#[test]
+fn test_rider_section_defaults_and_overrides() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[rider]
+min_wind = 15.0
+allowed_directions = ["cross", "cross-onshore"]
+    "#
+    )
+    .unwrap();
+
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+    assert_eq!(config.rider.min_wind, 15.0);
+    assert_eq!(config.rider.max_wind, RiderConfig::default().max_wind);
+    assert_eq!(config.rider.allowed_directions, vec!["cross", "cross-onshore"]);
+    assert!(config.rider.daylight_only);
+    assert!(validate_rider_config(&config.rider).is_ok());
+}
+
+#[test]
+fn test_invalid_rider_thresholds_return_errors() {
+    let inverted = RiderConfig {
+        min_wind: 30.0,
+        max_wind: 20.0,
+        ..RiderConfig::default()
+    };
+    assert!(format!("{:#}", validate_rider_config(&inverted).unwrap_err()).contains("min_wind"));
+
+    let gust = RiderConfig {
+        max_gust_factor: 0.8,
+        ..RiderConfig::default()
+    };
+    assert!(format!("{:#}", validate_rider_config(&gust).unwrap_err()).contains("max_gust_factor"));
+
+    let direction = RiderConfig {
+        allowed_directions: vec!["sideshore".to_string()],
+        ..RiderConfig::default()
+    };
+    let message = format!("{:#}", validate_rider_config(&direction).unwrap_err());
+    assert!(message.contains("allowed_directions"));
+    assert!(message.contains("sideshore"));
+}
+
+#[test]
+fn test_rider_profile_and_quiver_are_loaded() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[rider]
+weight = 68.5
+skill = "beginner"
+sails = [4.5, 5.3]
+
+[[rider.boards]]
+name = "Freeride 130"
+volume = 130.0
+    "#
+    )
+    .unwrap();
+
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+    assert_eq!(config.rider.weight, Some(68.5));
+    assert_eq!(config.rider.skill, SkillLevel::Beginner);
+    assert_eq!(config.rider.sails, vec![4.5, 5.3]);
+    assert_eq!(config.rider.boards[0].name, "Freeride 130");
+    assert!(validate_rider_config(&config.rider).is_ok());
+
+    let negative = RiderConfig {
+        sails: vec![5.3, -1.0],
+        ..config.rider
+    };
+    assert!(format!("{:#}", validate_rider_config(&negative).unwrap_err()).contains("sail sizes"));
+}
+
+// ============================================================================
+// Test Pattern 11: Response Cache
+// ============================================================================
+
+#[test]
+fn test_cache_section_and_flags_resolve() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[cache]
+ttl_minutes = 30
+dir = "/tmp/windsurf-cache"
+    "#
+    )
+    .unwrap();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let mut args = create_valid_args();
+    let cache = resolve_cache_settings(&args, &config.cache);
+    assert_eq!(cache.dir, PathBuf::from("/tmp/windsurf-cache"));
+    assert_eq!(cache.ttl, Duration::from_secs(30 * 60));
+    assert_eq!(cache.mode, CacheMode::ReadWrite);
+
+    args.refresh = true;
+    assert_eq!(resolve_cache_settings(&args, &config.cache).mode, CacheMode::Refresh);
+    args.no_cache = true;
+    assert_eq!(resolve_cache_settings(&args, &config.cache).mode, CacheMode::Disabled);
+    args.offline = true;
+    assert_eq!(resolve_cache_settings(&args, &config.cache).mode, CacheMode::Offline);
+
+    assert_eq!(CacheConfig::default().ttl_minutes, 180);
+
+    // An absurd lifetime saturates rather than overflowing
+    let forever = CacheConfig { ttl_minutes: u64::MAX, ..CacheConfig::default() };
+    assert_eq!(resolve_cache_settings(&args, &forever).ttl, Duration::from_secs(u64::MAX));
+}
+
+// ============================================================================
+// Test Pattern 12: API Endpoints
+// ============================================================================
+
+#[test]
+fn test_endpoints_section_resolves() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[endpoints]
+stormglass = "http://localhost:8080/stormglass/v2/"
+windy = "https://proxy.example.com/windy"
+    "#
+    )
+    .unwrap();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let endpoints = resolve_endpoints(&config.endpoints).unwrap();
+    assert_eq!(endpoints["stormglass"], "http://localhost:8080/stormglass/v2");
+    assert_eq!(endpoints["windy"], "https://proxy.example.com/windy");
+    assert!(!endpoints.contains_key("openweathermap"));
+}
+
+#[test]
+fn test_endpoints_reject_unknown_provider_and_bad_url() {
+    let endpoints = BTreeMap::from([("composite".to_string(), "http://localhost".to_string())]);
+    let err = resolve_endpoints(&endpoints).unwrap_err().to_string();
+    assert!(err.contains("Unknown provider 'composite' in [endpoints]"), "{}", err);
+
+    let endpoints = BTreeMap::from([("windy".to_string(), "ftp://mirror/windy".to_string())]);
+    let err = format!("{:#}", resolve_endpoints(&endpoints).unwrap_err());
+    assert!(err.contains("Invalid [endpoints] URL for 'windy'"), "{}", err);
+    assert!(err.contains("must be an http or https URL"), "{}", err);
+}
+
+#[test]
+fn test_endpoint_environment_overrides_config() {
+    // Only this test reads OPEN_METEO_MARINE_API_URL
+    std::env::set_var("OPEN_METEO_MARINE_API_URL", "http://127.0.0.1:9000/v1");
+    let endpoints = BTreeMap::from([(
+        "openmeteo-marine".to_string(),
+        "https://mirror.example.com/v1".to_string(),
+    )]);
+    let resolved = resolve_endpoints(&endpoints);
+    std::env::remove_var("OPEN_METEO_MARINE_API_URL");
+
+    assert_eq!(resolved.unwrap()["openmeteo-marine"], "http://127.0.0.1:9000/v1");
+}
+
+// ============================================================================
+// Test Pattern 13: HTTP Timeout and Retries
+// ============================================================================
+
+#[test]
+fn test_http_section_resolves_retry_policy() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"
+[http]
+max_attempts = 5
+timeout_seconds = 10
+    "#
+    )
+    .unwrap();
+    let config = load_config_from_file(Some(&temp_file.path().to_path_buf())).unwrap();
+
+    let retry = resolve_retry_policy(&config.http).unwrap();
+    assert_eq!(retry.max_attempts, 5);
+    assert_eq!(retry.timeout, Duration::from_secs(10));
+
+    let defaults = resolve_retry_policy(&HttpConfig::default()).unwrap();
+    assert_eq!(defaults.max_attempts, 3);
+    assert_eq!(defaults.timeout, Duration::from_secs(30));
+
+    let err = resolve_retry_policy(&HttpConfig { max_attempts: 0, ..HttpConfig::default() }).unwrap_err();
+    assert!(err.to_string().contains("max_attempts must be at least 1"), "{}", err);
+}
@@ -0,0 +1,129 @@
+// ============================================================================
+// Provider Endpoint Override Tests
+// ============================================================================
+//
+// Points providers at a local httpmock stand-in through their base URL
+// overrides and checks the paths, parameters and headers they send.
+
+use chrono::{DateTime, Utc};
+use httpmock::prelude::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+use windsurf_forecast::forecast_provider::ForecastProvider;
+use windsurf_forecast::provider_registry::create_provider;
+use windsurf_forecast::providers::openweathermap::OpenWeatherMapProvider;
+use windsurf_forecast::providers::stormglass::StormGlassProvider;
+use windsurf_forecast::providers::windy::WindyProvider;
+use windsurf_forecast::test_utils::*;
+
+fn window() -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        "2025-12-06T22:00:00Z".parse().unwrap(),
+        "2025-12-08T22:00:00Z".parse().unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_stormglass_uses_base_url() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(GET)
+                .path("/v2/weather/point")
+                .header("Authorization", "sg-key")
+                .query_param("lat", "32.486722")
+                .query_param("source", "sg");
+            then.status(200).json_body(mock_stormglass_complete_response());
+        })
+        .await;
+
+    let provider = StormGlassProvider::new("sg-key".to_string()).with_base_url(&server.url("/v2"));
+    let (start, end) = window();
+    let data = provider
+        .fetch_weather_data(start, end, 32.486722, 34.888722, chrono_tz::UTC)
+        .await
+        .unwrap();
+
+    assert_eq!(mock.hits_async().await, 1);
+    assert_eq!(data.data_points.len(), 2);
+}
+
+#[tokio::test]
+async fn test_openweathermap_uses_base_url() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(GET)
+                .path("/data/3.0/onecall")
+                .query_param("appid", "owm-key")
+                .query_param("units", "metric");
+            then.status(401).body(r#"{"cod":401,"message":"Invalid API key"}"#);
+        })
+        .await;
+
+    let provider = OpenWeatherMapProvider::new("owm-key".to_string())
+        .with_base_url(&server.url("/data/3.0"));
+    let (start, end) = window();
+    let err = provider
+        .fetch_weather_data(start, end, 32.486722, 34.888722, chrono_tz::UTC)
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits_async().await, 1);
+    assert!(err.to_string().contains("Invalid API key"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_windy_appends_point_forecast_path() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/api/point-forecast/v2")
+                .json_body_partial(r#"{"key": "windy-key"}"#);
+            then.status(400).body("invalid model");
+        })
+        .await;
+
+    let provider = WindyProvider::new("windy-key".to_string()).with_base_url(&server.url("/api"));
+    let (start, end) = window();
+    let err = provider
+        .fetch_weather_data(start, end, 32.486722, 34.888722, chrono_tz::UTC)
+        .await
+        .unwrap_err();
+
+    // Both model requests go to the same endpoint
+    assert_eq!(mock.hits_async().await, 2);
+    assert!(err.to_string().contains("invalid model"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_registry_applies_configured_endpoint() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(GET).path("/v1/forecast").query_param("latitude", "32.486722");
+            then.status(200).json_body(json!({
+                "hourly": {
+                    "time": [1765101600],
+                    "temperature_2m": [21.0],
+                    "wind_speed_10m": [6.5],
+                    "wind_direction_10m": [275.0]
+                }
+            }));
+        })
+        .await;
+
+    let mut config = create_resolved_config();
+    config.provider = "openmeteo".to_string();
+    config.endpoints = BTreeMap::from([("openmeteo".to_string(), server.url("/v1"))]);
+    let provider = create_provider("openmeteo", &config).unwrap();
+    let (start, end) = window();
+    let data = provider
+        .fetch_weather_data(start, end, config.lat, config.lng, config.timezone)
+        .await
+        .unwrap();
+
+    assert_eq!(mock.hits_async().await, 1);
+    assert_eq!(data.data_points.len(), 1);
+}
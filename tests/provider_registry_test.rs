@@ -9,6 +9,9 @@ use windsurf_forecast::provider_registry::{
     all_provider_descriptions, all_provider_names, check_duplicates, create_provider,
     get_provider_metadata, validate_provider_name,
 };
+use windsurf_forecast::config::loader::CompositeConfig;
+use windsurf_forecast::config::resolver::validate_composite_config;
+use windsurf_forecast::test_utils::create_resolved_config;
 
 // ============================================================================
 // Provider Discovery Tests
@@ -22,7 +25,7 @@ fn test_get_provider_metadata_for_stormglass() {
     let meta = meta.unwrap();
     assert_eq!(meta.name, "stormglass");
     assert!(!meta.description.is_empty());
-    assert_eq!(meta.api_key_var, "STORMGLASS_API_KEY");
+    assert_eq!(meta.api_key_var, Some("STORMGLASS_API_KEY"));
 }
 
 #[test]
@@ -36,7 +39,17 @@ fn test_get_provider_metadata_for_openweathermap() {
     let meta = meta.unwrap();
     assert_eq!(meta.name, "openweathermap");
     assert!(!meta.description.is_empty());
-    assert_eq!(meta.api_key_var, "OPEN_WEATHER_MAP_API_KEY");
+    assert_eq!(meta.api_key_var, Some("OPEN_WEATHER_MAP_API_KEY"));
+}
+
+#[test]
+fn test_get_provider_metadata_for_openmeteo_is_keyless() {
+    let meta = get_provider_metadata("openmeteo");
+    assert!(meta.is_some(), "Open-Meteo provider should be registered");
+
+    let meta = meta.unwrap();
+    assert_eq!(meta.name, "openmeteo");
+    assert!(meta.api_key_var.is_none(), "Open-Meteo should not need an API key");
 }
 
 #[test]
@@ -152,7 +165,7 @@ fn test_validate_provider_name_error_lists_available_providers() {
 
 #[test]
 fn test_create_provider_with_unknown_name_returns_error() {
-    let result = create_provider("nonexistent");
+    let result = create_provider("nonexistent", &create_resolved_config());
     assert!(result.is_err(), "Unknown provider should fail");
 
     if let Err(e) = result {
@@ -172,9 +185,50 @@ fn test_create_provider_with_unknown_name_returns_error() {
     }
 }
 
+#[test]
+fn test_create_keyless_provider_succeeds_without_env() {
+    let provider = create_provider("openmeteo", &create_resolved_config());
+    assert!(provider.is_ok(), "Keyless provider should instantiate without API key");
+    assert_eq!(provider.unwrap().name(), "openmeteo");
+}
+
+#[test]
+fn test_create_composite_provider_from_mapping() {
+    let mut config = create_resolved_config();
+    config.provider = "composite".to_string();
+    config.composite = CompositeConfig {
+        wind: Some("openmeteo".to_string()),
+        waves: Some("openmeteo-marine".to_string()),
+        ..Default::default()
+    };
+
+    let provider = create_provider("composite", &config);
+    assert!(provider.is_ok(), "Composite of keyless providers should instantiate");
+    assert_eq!(provider.unwrap().name(), "composite");
+}
+
+#[test]
+fn test_validate_composite_config_rejects_empty_and_unknown() {
+    assert!(validate_composite_config(&CompositeConfig::default()).is_err());
+
+    let unknown = CompositeConfig {
+        clouds: Some("nonexistent".to_string()),
+        ..Default::default()
+    };
+    let err_msg = format!("{:#}", validate_composite_config(&unknown).unwrap_err());
+    assert!(err_msg.contains("clouds"), "Error should name the field");
+    assert!(err_msg.contains("nonexistent"), "Error should name the provider");
+
+    let recursive = CompositeConfig {
+        wind: Some("composite".to_string()),
+        ..Default::default()
+    };
+    assert!(validate_composite_config(&recursive).is_err());
+}
+
 #[test]
 fn test_create_provider_error_lists_available_providers() {
-    let result = create_provider("invalid");
+    let result = create_provider("invalid", &create_resolved_config());
     assert!(result.is_err());
 
     if let Err(e) = result {
@@ -222,9 +276,17 @@ fn test_all_registered_providers_have_valid_metadata() {
         );
         
         let meta = meta.unwrap();
-        assert!(
-            !meta.api_key_var.is_empty(),
-            "Provider {} should have API key var",
+        if let Some(api_key_var) = meta.api_key_var {
+            assert!(
+                !api_key_var.is_empty(),
+                "Provider {} should have non-empty API key var",
+                name
+            );
+        }
+        assert_eq!(
+            meta.base_url.is_some(),
+            meta.base_url_var.is_some(),
+            "Provider {} should have both a base URL and its override variable, or neither",
             name
         );
     }
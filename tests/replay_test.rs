@@ -0,0 +1,134 @@
+// ============================================================================
+// Provider Replay Tests (Golden Files)
+// ============================================================================
+//
+// Runs `fetch_weather_data` end to end against the exchanges recorded in
+// tests/fixtures/replay (as written by `--record`), so the real request,
+// status handling and parsing code of each provider is exercised without
+// network access or API keys.
+
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+use windsurf_forecast::forecast_provider::{ForecastProvider, WeatherData};
+use windsurf_forecast::http::{replay_time, FixtureMode, HttpClient};
+use windsurf_forecast::providers::openweathermap::OpenWeatherMapProvider;
+use windsurf_forecast::providers::stormglass::StormGlassProvider;
+use windsurf_forecast::providers::windy::WindyProvider;
+use windsurf_forecast::units::SpeedUnit;
+
+const LAT: f64 = 32.486722;
+const LNG: f64 = 34.888722;
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/replay")
+}
+
+fn replay() -> HttpClient {
+    HttpClient::default().with_fixtures(FixtureMode::Replay(fixtures()))
+}
+
+fn window() -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        "2025-12-06T22:00:00Z".parse().unwrap(),
+        "2025-12-08T22:00:00Z".parse().unwrap(),
+    )
+}
+
+async fn fetch(provider: &dyn ForecastProvider) -> WeatherData {
+    let (start, end) = window();
+    provider
+        .fetch_weather_data(start, end, LAT, LNG, chrono_tz::Asia::Jerusalem)
+        .await
+        .unwrap()
+}
+
+fn local_times(data: &WeatherData) -> Vec<String> {
+    data.data_points
+        .iter()
+        .map(|point| point.time.local().format("%Y-%m-%d %H:%M").to_string())
+        .collect()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 0.01, "expected {}, got {}", expected, actual);
+}
+
+// ============================================================================
+// Test Pattern 1: Recorded Providers
+// ============================================================================
+
+#[tokio::test]
+async fn test_stormglass_replay() {
+    let provider = StormGlassProvider::new("unused".to_string()).with_http(replay());
+    let data = fetch(&provider).await;
+
+    assert_eq!(local_times(&data), ["2025-12-07 12:00", "2025-12-07 13:00", "2025-12-07 14:00"]);
+    let first = &data.data_points[0];
+    // m/s as in the payload; conversion is left to the output units
+    assert_close(first.wind.wind_speed.unwrap().value(), 6.2);
+    assert_close(first.wind.gust.unwrap().value(), 8.1);
+    assert_close(first.wind.wind_direction.unwrap().value(), 268.0);
+    assert_close(first.waves.swell_height.unwrap().value(), 0.93);
+    assert_close(first.water_temperature.unwrap().value(), 21.9);
+    assert_eq!(first.clouds.cloud_cover, Some(12.0));
+    assert!(data.alerts.is_none());
+}
+
+#[tokio::test]
+async fn test_openweathermap_replay() {
+    let provider = OpenWeatherMapProvider::new("unused".to_string()).with_http(replay());
+    let data = fetch(&provider).await;
+
+    assert_eq!(local_times(&data), ["2025-12-07 12:00", "2025-12-07 13:00", "2025-12-07 14:00"]);
+    let last = &data.data_points[2];
+    assert_close(last.wind.wind_speed.unwrap().value(), 6.8);
+    assert_close(last.wind.gust.unwrap().value(), 9.3);
+    assert_close(last.wind.wind_direction.unwrap().value(), 280.0);
+    // feels_like is reported as the air temperature
+    assert_close(last.air_temperature.unwrap().value(), 21.8);
+
+    let alerts = data.alerts.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].event, "Strong wind warning");
+    assert_eq!(alerts[0].start.local().format("%H:%M").to_string(), "14:00");
+}
+
+#[tokio::test]
+async fn test_windy_replay() {
+    let provider = WindyProvider::new("unused".to_string()).with_http(replay());
+    let data = fetch(&provider).await;
+
+    assert_eq!(local_times(&data), ["2025-12-07 12:00", "2025-12-07 13:00", "2025-12-07 14:00"]);
+    let first = &data.data_points[0];
+    // Pure eastward u component: wind from the west
+    assert_close(first.wind.wind_speed.unwrap().to(SpeedUnit::MetersPerSecond).value(), 6.0);
+    assert_close(first.wind.wind_direction.unwrap().value(), 270.0);
+    assert_close(first.air_temperature.unwrap().value(), 21.4);
+    assert_close(first.waves.swell_height.unwrap().value(), 0.91);
+    // Windy reports null wind waves for the second hour
+    assert!(data.data_points[1].waves.wind_wave_height.is_none());
+    assert_close(data.data_points[2].waves.wind_wave_height.unwrap().value(), 0.38);
+}
+
+// ============================================================================
+// Test Pattern 2: Replay Bookkeeping
+// ============================================================================
+
+#[test]
+fn test_replay_time_is_earliest_recording() {
+    let recorded_at: DateTime<Utc> = "2025-12-07T09:12:44.518Z".parse().unwrap();
+    assert_eq!(replay_time(&fixtures()).unwrap(), recorded_at);
+}
+
+#[tokio::test]
+async fn test_replay_without_recording_fails() {
+    let provider = StormGlassProvider::new("unused".to_string())
+        .with_tides(true)
+        .with_http(replay());
+    let (start, end) = window();
+    let err = provider
+        .fetch_weather_data(start, end, LAT, LNG, chrono_tz::UTC)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("Replay: no recorded stormglass tide-extremes response"), "{:#}", err);
+}
@@ -110,7 +110,10 @@ fn test_local_timestamp_serialization_format() {
 
 #[test]
 fn test_weather_data_point_timestamp_serialization() {
-    use windsurf_forecast::forecast_provider::WeatherDataPoint;
+    use windsurf_forecast::forecast_provider::{
+        CloudDatapointSection, WaveDatapointSection, WeatherDataPoint, WindDatapoinSection,
+    };
+    use windsurf_forecast::units::{degrees, Speed, SpeedUnit, Temperature, TemperatureUnit};
     
     let utc = UtcTimestamp::from_rfc3339("2025-12-07T12:00:00Z").unwrap();
     let target_tz: Tz = "Asia/Jerusalem".parse().unwrap();
@@ -118,16 +121,33 @@ fn test_weather_data_point_timestamp_serialization() {
     
     let data_point = WeatherDataPoint {
         time: local,
-        air_temperature: Some(22.5),
-        wind_speed: Some(10.0),
-        wind_direction: Some(270.0),
-        gust: None,
-        swell_height: None,
-        swell_period: None,
-        swell_direction: None,
+        air_temperature: Some(Temperature::new(22.5, TemperatureUnit::Celsius)),
+        wind: WindDatapoinSection {
+            wind_speed: Some(Speed::new(10.0, SpeedUnit::MetersPerSecond)),
+            wind_direction: Some(degrees(270.0)),
+            gust: None,
+            shore_wind: None,
+        },
+        waves: WaveDatapointSection {
+            swell_height: None,
+            swell_period: None,
+            swell_direction: None,
+            wind_wave_height: None,
+            wind_wave_period: None,
+            wind_wave_direction: None,
+        },
         water_temperature: None,
-        cloud_cover: None,
+        clouds: CloudDatapointSection {
+            cloud_cover: None,
+            low_cloud_cover: None,
+            medium_cloud_cover: None,
+            high_cloud_cover: None,
+        },
         precipitation: None,
+        tide_height: None,
+        is_daylight: None,
+        sailability: None,
+        gear: None,
     };
     
     let json = serde_json::to_string(&data_point).unwrap();